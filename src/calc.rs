@@ -1,12 +1,21 @@
+use crate::context::WinContext;
 use crate::fu::{calculate_total_fu_value, Fu};
 use crate::hand::error::HandErr;
 use crate::hand::Hand;
 use crate::limit_hand::LimitHands;
+use crate::pao::{liable_player, Pao};
 use crate::payment::Payment;
-use crate::score::{FuValue, HanValue, HonbaCounter, Score};
+use crate::ruleset::{NagashiMangan, Ruleset};
+use crate::score::{Dora, FuValue, HanValue, Score};
 use crate::tile_group::TileGroup;
 use crate::yaku::Yaku;
 
+/// Han each yakuman counts as in aotenjou.
+const AOTENJOU_YAKUMAN_HAN: HanValue = 13;
+
+/// Fu a hand can score once rounded, chiitoitsu scoring 25.
+pub const FU_VALUES: [FuValue; 11] = [20, 25, 30, 40, 50, 60, 70, 80, 90, 100, 110];
+
 #[derive(Debug, PartialEq)]
 pub enum CalculatorErrors {
     NoHan,
@@ -25,34 +34,18 @@ impl std::fmt::Display for CalculatorErrors {
 }
 
 /// Get the score breakdown of the hand.
-pub fn get_hand_score(
-    hand: Hand,
-    dora: Option<Vec<TileGroup>>,
-    tsumo: bool,
-    riichi: bool,
-    doubleriichi: bool,
-    ippatsu: bool,
-    haitei: bool,
-    rinshan: bool,
-    chankan: bool,
-    tenhou: bool,
-    honba: HonbaCounter,
-) -> Result<Score, HandErr> {
-    if hand.kans().is_empty() && rinshan {
-        return Err(HandErr::RinshanKanWithoutKan);
-    }
-
-    let yaku = get_yaku_han(
-        &hand,
-        riichi,
-        doubleriichi,
-        ippatsu,
-        haitei,
-        rinshan,
-        chankan,
-        tenhou,
-        tsumo,
-    );
+///
+/// Every wait the winning tile could have completed is scored and the highest scoring one is kept.
+pub fn get_hand_score(hand: Hand, context: &WinContext, rules: &Ruleset) -> Result<Score, HandErr> {
+    get_best_hand_score(vec![hand], context, rules).map(|best| best.score)
+}
+
+/// Get the score breakdown of the hand with the wait it was built with.
+fn score_hand(hand: &Hand, context: &WinContext, rules: &Ruleset) -> Result<Score, HandErr> {
+    context.check_hand(hand)?;
+    rules.check_hand(hand, context)?;
+
+    let yaku = get_yaku_han(hand, context, rules);
 
     if yaku.0 == 0 {
         return Err(HandErr::NoYaku);
@@ -64,20 +57,25 @@ pub fn get_hand_score(
         if yaku.1.contains(&Yaku::Chiitoitsu) {
             vec![Fu::BasePointsChitoi]
         } else if yaku.1.contains(&Yaku::Pinfu) {
-            if tsumo {
+            if context.tsumo() {
                 vec![Fu::BasePoints]
             } else {
                 vec![Fu::BasePoints, Fu::ClosedRon]
             }
         } else {
-            hand.calculate_fu(tsumo)
+            hand.calculate_fu(context.tsumo())
         }
     };
 
-    // get han from dora tiles
-    let dora_count = hand.get_dora_count(dora);
+    // get han from dora and ura dora tiles, red fives and nukidora
+    let dora = Dora {
+        dora: indicator_dora(hand, context.dora(), context.nukidora(), rules)?,
+        aka: hand.aka_count(),
+        ura: indicator_dora(hand, context.ura_dora(), context.nukidora(), rules)?,
+        nukidora: context.nukidora(),
+    };
 
-    let han = yaku.0 + dora_count;
+    let han = yaku.0 + dora.total();
     let fu_value = calculate_total_fu_value(&fu);
 
     let mut has_yakuman = false;
@@ -87,11 +85,21 @@ pub fn get_hand_score(
         }
     }
 
-    let payment = if has_yakuman {
-        calculate_yakuman(&yaku.1)?
+    let pao = if has_yakuman && !rules.aotenjou {
+        liable_player(hand, &yaku.1, context.called_from())
     } else {
-        //can unwrap here because check for yaku earlier
-        calculate(han, fu_value).unwrap()
+        None
+    };
+    let (payment, limit_hand) = if has_yakuman && !rules.aotenjou {
+        (
+            calculate_yakuman(&yaku.1, rules, pao.as_ref())?,
+            Some(LimitHands::Yakuman(yakuman_count(&yaku.1, rules))),
+        )
+    } else {
+        (
+            calculate(han, fu_value, rules)?,
+            LimitHands::get_limit_hand(han, fu_value, rules),
+        )
     };
     let score = Score::new(
         payment,
@@ -99,36 +107,101 @@ pub fn get_hand_score(
         fu,
         han,
         fu_value,
-        honba,
+        context.honba(),
         hand.is_open(),
-        dora_count,
-    );
+        dora,
+        limit_hand,
+        pao,
+    )
+    .with_riichi_sticks(context.riichi_sticks());
+    if !score.is_countable() {
+        return Err(HandErr::ScoreOverflow);
+    }
 
     Ok(score)
 }
 
-/// Get the yaku score and list of yaku given a hand and some round context.
-pub fn get_yaku_han(
-    hand: &Hand,
-    riichi: bool,
-    doubleriichi: bool,
-    ippatsu: bool,
-    haitei: bool,
-    rinshan: bool,
-    chankan: bool,
-    tenhou: bool,
-    tsumo: bool,
-) -> (HanValue, Vec<Yaku>) {
+/// A reading of the hand's tiles together with the result of scoring it.
+#[derive(Debug)]
+pub struct Interpretation {
+    /// The grouping of the tiles that was scored.
+    pub hand: Hand,
+    /// The score, or the reason the grouping could not be scored.
+    pub score: Result<Score, HandErr>,
+}
+
+/// The highest scoring interpretation of a hand and the alternatives that lost out to it.
+#[derive(Debug)]
+pub struct BestScore {
+    /// The grouping that was scored.
+    pub hand: Hand,
+    /// The score of the best grouping.
+    pub score: Score,
+    /// Every other grouping, from the highest scoring to the lowest.
+    pub rejected: Vec<Interpretation>,
+}
+
+/// Score every interpretation of the hand and keep the highest scoring one.
+///
+/// The hands are usually every decomposition from [`crate::decompose::decompose_hand`], each of them is scored with every wait the winning tile could have completed.
+/// Interpretations are ranked on their payment, then han, then fu.
+pub fn get_best_hand_score(
+    hands: Vec<Hand>,
+    context: &WinContext,
+    rules: &Ruleset,
+) -> Result<BestScore, HandErr> {
+    let mut interpretations: Vec<Interpretation> = hands
+        .iter()
+        .flat_map(|hand| hand.waits().into_iter().map(|wait| hand.with_wait(wait)))
+        .map(|hand| {
+            let score = score_hand(&hand, context, rules);
+            Interpretation { hand, score }
+        })
+        .collect();
+
+    // scored interpretations first, highest value first; the sort is stable so errors keep their order
+    interpretations.sort_by_key(|interpretation| {
+        std::cmp::Reverse(
+            interpretation
+                .score
+                .as_ref()
+                .ok()
+                .map(|score| (score.payment().base_points(), score.han(), score.fu_score())),
+        )
+    });
+
+    if interpretations.is_empty() {
+        return Err(HandErr::InvalidShape);
+    }
+    let best = interpretations.remove(0);
+    let score = best.score?;
+
+    Ok(BestScore {
+        hand: best.hand,
+        score,
+        rejected: interpretations,
+    })
+}
+
+/// Get the yaku score and list of yaku given a hand, some round context and the ruleset.
+///
+/// A hand with yakuman only gets its yakuman, unless the ruleset is aotenjou where each yakuman is 13 han on top of the other yaku.
+pub fn get_yaku_han(hand: &Hand, context: &WinContext, rules: &Ruleset) -> (HanValue, Vec<Yaku>) {
+    let tsumo = context.tsumo();
     let mut yaku: Vec<Yaku> = vec![];
 
     let conditions = [
-        (riichi, Yaku::Riichi),
-        (doubleriichi, Yaku::DoubleRiichi),
-        (ippatsu, Yaku::Ippatsu),
-        (haitei, Yaku::Haitei),
-        (rinshan, Yaku::RinshanKaihou),
-        (chankan, Yaku::Chankan),
-        (hand.is_tanyao(), Yaku::Tanyao),
+        (context.riichi(), Yaku::Riichi),
+        (context.doubleriichi(), Yaku::DoubleRiichi),
+        (context.ippatsu(), Yaku::Ippatsu),
+        (context.haitei(), Yaku::Haitei),
+        (context.houtei(), Yaku::Houtei),
+        (context.rinshan(), Yaku::RinshanKaihou),
+        (context.chankan(), Yaku::Chankan),
+        (
+            hand.is_tanyao() && (rules.kuitan || !hand.is_open()),
+            Yaku::Tanyao,
+        ),
         (hand.is_iipeikou(), Yaku::Iipeikou),
         (hand.is_ryanpeikou(), Yaku::Ryanpeikou),
         (hand.is_toitoi(), Yaku::Toitoi),
@@ -154,20 +227,32 @@ pub fn get_yaku_han(
     let yakumanconditions = [
         (hand.is_daisangen(), Yaku::Daisangen),
         (hand.is_suuankou(tsumo), Yaku::Suuankou),
-        (hand.is_suuankoutankiwait(), Yaku::SuuankouTankiWait),
+        (
+            hand.is_suuankoutankiwait() && rules.double_yakuman,
+            Yaku::SuuankouTankiWait,
+        ),
         (hand.is_chinroutou(), Yaku::Chinroutou),
         (hand.is_ryuuiisou(), Yaku::Ryuuiisou),
         (hand.is_chuurenpoutou(), Yaku::ChuurenPoutou),
-        (hand.is_chuurenpoutou9sided(), Yaku::ChuurenPoutou9SidedWait),
+        (
+            hand.is_chuurenpoutou9sided() && rules.double_yakuman,
+            Yaku::ChuurenPoutou9SidedWait,
+        ),
         (hand.is_tsuuiisou(), Yaku::Tsuuiisou),
         (hand.is_daichiishin(), Yaku::Daichiishin),
         (hand.is_suukantsu(), Yaku::Suukantsu),
-        (hand.is_shousuushii(), Yaku::Shousuushii),
+        (
+            hand.is_shousuushii() && !hand.is_daisuushii(),
+            Yaku::Shousuushii,
+        ),
         (hand.is_daisuushii(), Yaku::Daisuushii),
         (hand.is_kokushi(), Yaku::KokushiMusou),
-        (hand.is_kokushi13sided(), Yaku::KokushiMusou13SidedWait),
-        (hand.is_tenhou(tenhou), Yaku::Tenhou),
-        (hand.is_chiihou(tenhou), Yaku::Chiihou),
+        (
+            hand.is_kokushi13sided() && rules.double_yakuman,
+            Yaku::KokushiMusou13SidedWait,
+        ),
+        (hand.is_tenhou(context.tenhou()), Yaku::Tenhou),
+        (hand.is_chiihou(context.tenhou()), Yaku::Chiihou),
     ];
 
     for (condition, yaku_type) in yakumanconditions {
@@ -175,7 +260,7 @@ pub fn get_yaku_han(
             yakuman.push(yaku_type);
         }
     }
-    if !yakuman.is_empty() {
+    if !yakuman.is_empty() && !rules.aotenjou {
         return (yakuman.len() as HanValue, yakuman);
     }
 
@@ -194,29 +279,56 @@ pub fn get_yaku_han(
         yaku_han += y.get_han(hand.is_open());
     }
 
+    if !yakuman.is_empty() {
+        yaku_han += AOTENJOU_YAKUMAN_HAN * yakuman_count(&yakuman, rules);
+        yakuman.append(&mut yaku);
+        return (yaku_han, yakuman);
+    }
+
     (yaku_han, yaku)
 }
 
-/// Calculate the payment amounts from the list of yakuman yaku.
-pub fn calculate_yakuman(yaku: &Vec<Yaku>) -> Result<Payment, HandErr> {
+/// Count the yakuman in the list of yaku.
+///
+/// Daisuushii counts as a double yakuman when the ruleset has them.
+pub fn yakuman_count(yaku: &[Yaku], rules: &Ruleset) -> HanValue {
     let mut total = 0;
     for y in yaku {
-        if y.is_yakuman() {
+        if *y == Yaku::Daisuushii && rules.double_yakuman {
+            total += 2;
+        } else if y.is_yakuman() {
             total += y.get_han(false);
         }
     }
+    total
+}
+
+/// Calculate the payment amounts from the list of yakuman yaku.
+///
+/// With a liable (pao) player, the payment records the part of it they answer for.
+pub fn calculate_yakuman(
+    yaku: &[Yaku],
+    rules: &Ruleset,
+    pao: Option<&Pao>,
+) -> Result<Payment, HandErr> {
+    let total = yakuman_count(yaku, rules);
     if total == 0 {
         return Err(HandErr::NoYaku);
     }
 
-    let basepoints: u64 = (8_000 * total).into();
-    let payment = Payment::new(basepoints);
+    let mut payment = rules_payment(LimitHands::Yakuman(total).get_score(), rules);
+    if let Some(pao) = pao {
+        let liable = LimitHands::Yakuman(yakuman_count(&[pao.yaku], rules)).get_score();
+        payment = payment.with_pao(liable.base_points());
+    }
 
     Ok(payment)
 }
 
-/// Calculate the payment amounts from the han, fu, and number of honba (repeat counters).
-pub fn calculate(han: HanValue, fu: FuValue) -> Result<Payment, HandErr> {
+/// Calculate the payment amounts from the han and fu under the ruleset.
+///
+/// With aotenjou there are no limits, and a score too large to pay out is an error.
+pub fn calculate(han: HanValue, fu: FuValue, rules: &Ruleset) -> Result<Payment, HandErr> {
     if han == 0 {
         return Err(HandErr::NoHan);
     }
@@ -225,14 +337,472 @@ pub fn calculate(han: HanValue, fu: FuValue) -> Result<Payment, HandErr> {
         return Err(HandErr::NoFu);
     }
 
-    let k = LimitHands::get_limit_hand(han, fu);
+    let k = LimitHands::get_limit_hand(han, fu, rules);
     if let Some(limithand) = k {
-        let payment = limithand.get_score();
+        let payment = rules_payment(limithand.get_score(), rules);
 
         return Ok(payment);
     }
 
-    let payment = Payment::from_han_and_fu(han, fu);
+    let Some(payment) = Payment::checked_from_han_and_fu(han, fu) else {
+        return Err(HandErr::ScoreOverflow);
+    };
 
-    Ok(payment)
+    Ok(rules_payment(payment, rules))
+}
+
+/// Check a hand can score the han and fu when won by ron or tsumo.
+///
+/// 20 fu is only a closed pinfu tsumo, worth at least 2 han, and 25 fu is chiitoitsu, worth 2 han or 3 with tsumo.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::calc::is_possible_han_fu;
+///
+/// assert!(is_possible_han_fu(2, 20, true));
+/// assert!(!is_possible_han_fu(2, 20, false));
+/// assert!(!is_possible_han_fu(2, 25, true));
+/// assert!(is_possible_han_fu(1, 30, false));
+/// ```
+pub fn is_possible_han_fu(han: HanValue, fu: FuValue, tsumo: bool) -> bool {
+    match fu {
+        20 => tsumo && han >= 2,
+        25 => han >= if tsumo { 3 } else { 2 },
+        _ => han >= 1 && FU_VALUES.contains(&fu),
+    }
+}
+
+/// Score nagashi mangan from a player's discards, paid like a mangan tsumo.
+///
+/// Every discard has to be a terminal or honor and `called` is whether another player called any of them.
+/// When the ruleset makes it a draw the honba and riichi sticks of the context are not paid.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::calc::nagashi_mangan;
+/// use mahc::context::WinContext;
+/// use mahc::decompose::parse_tiles;
+/// use mahc::ruleset::Ruleset;
+///
+/// let discards = parse_tiles("19m1p9s1234567z").unwrap();
+/// let context = WinContext::builder().honba(1).build().unwrap();
+/// let score = nagashi_mangan(&discards, false, &context, &Ruleset::default()).unwrap();
+/// assert_eq!(score.payment().non_dealer_tsumo_to_dealer(score.honba()), 4_000);
+///
+/// let discards = parse_tiles("19m5p").unwrap();
+/// assert!(nagashi_mangan(&discards, false, &context, &Ruleset::default()).is_err());
+/// ```
+pub fn nagashi_mangan(
+    discards: &[TileGroup],
+    called: bool,
+    context: &WinContext,
+    rules: &Ruleset,
+) -> Result<Score, HandErr> {
+    let Some(nagashi) = rules.nagashi_mangan else {
+        return Err(HandErr::NoNagashiMangan);
+    };
+    if called
+        || discards.is_empty()
+        || !discards
+            .iter()
+            .all(|tile| tile.isterminal || tile.is_honor())
+    {
+        return Err(HandErr::NotNagashiMangan);
+    }
+
+    let (honba, riichi_sticks) = match nagashi {
+        NagashiMangan::Win => (context.honba(), context.riichi_sticks()),
+        NagashiMangan::Draw => (0, 0),
+    };
+    let yaku = Yaku::NagashiMangan;
+    let score = Score::new(
+        rules_payment(LimitHands::Mangan.get_score(), rules),
+        vec![yaku],
+        vec![],
+        yaku.get_han(false),
+        0,
+        honba,
+        false,
+        Dora::default(),
+        Some(LimitHands::Mangan),
+        None,
+    )
+    .with_riichi_sticks(riichi_sticks);
+    if !score.is_countable() {
+        return Err(HandErr::ScoreOverflow);
+    }
+
+    Ok(score)
+}
+
+/// Set the honba value and the sanma tsumo split of the ruleset on a payment.
+pub(crate) fn rules_payment(payment: Payment, rules: &Ruleset) -> Payment {
+    payment
+        .with_tsumibou(rules.honba_value)
+        .with_sanma(rules.sanma)
+}
+
+/// Count the dora the indicators give in the hand and in the north tiles set aside (nukidora).
+///
+/// In sanma the 1m indicator points to 9m.
+fn indicator_dora(
+    hand: &Hand,
+    indicators: &[TileGroup],
+    nukidora: u32,
+    rules: &Ruleset,
+) -> Result<u32, HandErr> {
+    let mut dora_tiles = vec![];
+    for indicator in indicators {
+        dora_tiles.push(if rules.sanma.is_some() {
+            indicator.next_tile_sanma()?
+        } else {
+            indicator.next_tile()?
+        });
+    }
+    let north: TileGroup = "Nw".to_string().try_into()?;
+    let north_dora = dora_tiles
+        .iter()
+        .filter(|tile| tile.tile_index() == north.tile_index())
+        .count() as u32;
+
+    Ok(hand.dora_tile_count(&dora_tiles) + nukidora * north_dora)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::CalledMeld;
+    use crate::decompose::decompose_hand;
+    use crate::hand::test_hand;
+    use crate::hand::wait::WaitType;
+
+    fn best_ron(tiles: &str, win: &str) -> Result<BestScore, HandErr> {
+        let hands = decompose_hand(
+            tiles,
+            vec![],
+            win.to_string(),
+            "Ew".to_string(),
+            "Ww".to_string(),
+        )?;
+        get_best_hand_score(hands, &WinContext::default(), &Ruleset::default())
+    }
+
+    #[test]
+    fn best_score_triplets_over_sequences() {
+        let best = best_ron("222333444m5599p", "9p").unwrap();
+        assert_eq!(best.score.yaku(), [Yaku::Toitoi, Yaku::Sanankou]);
+        assert_eq!(best.rejected.len(), 1);
+        assert_eq!(
+            best.rejected[0].score.as_ref().unwrap().yaku(),
+            [Yaku::Iipeikou]
+        );
+    }
+
+    #[test]
+    fn best_score_ryanpeikou_over_chiitoitsu() {
+        let best = best_ron("223344m556677p11s", "1s").unwrap();
+        assert_eq!(best.score.yaku(), [Yaku::Ryanpeikou]);
+        assert_eq!(best.score.fu_score(), 40);
+
+        let chiitoitsu = best.rejected[0].score.as_ref().unwrap();
+        assert_eq!(chiitoitsu.yaku(), [Yaku::Chiitoitsu]);
+        assert_eq!(chiitoitsu.fu_score(), 25);
+        assert_eq!(chiitoitsu.payment().non_dealer_ron(0), 1_600);
+    }
+
+    #[test]
+    fn hand_score_picks_best_wait() {
+        // the 1m completes either the 111m triplet on a shanpon or the 123m on a ryanmen, only the ryanmen keeps sanankou
+        let hand = Hand::new_from_strings(
+            vec![
+                "123m".to_string(),
+                "444p".to_string(),
+                "777s".to_string(),
+                "99s".to_string(),
+                "111m".to_string(),
+            ],
+            "1m".to_string(),
+            "Ew".to_string(),
+            "Ww".to_string(),
+        )
+        .unwrap();
+        assert_eq!(hand.wait().wait_type, WaitType::Shanpon);
+        assert!(!hand.is_sanankou(false));
+
+        let score = get_hand_score(hand, &WinContext::default(), &Ruleset::default()).unwrap();
+        assert_eq!(score.yaku(), [Yaku::Sanankou]);
+    }
+
+    #[test]
+    fn best_score_keeps_yakuless_alternatives() {
+        let best = best_ron("123m456p789s2224z", "4z").unwrap_err();
+        assert_eq!(best, HandErr::NoYaku);
+
+        // the 3m completes either a penchan 123m or a ryanmen 345m, only the ryanmen gives pinfu
+        let best = best_ron("12345m456p789s11s", "3m").unwrap();
+        assert_eq!(best.score.yaku(), [Yaku::Pinfu]);
+        assert_eq!(best.hand.wait().wait_type, WaitType::Ryanmen);
+        assert_eq!(best.rejected.len(), 1);
+        assert_eq!(
+            best.rejected[0].score.as_ref().unwrap_err(),
+            &HandErr::NoYaku
+        );
+    }
+
+    #[test]
+    fn last_tile_haitei_or_houtei() {
+        let hand = Hand::new_from_strings(
+            vec![
+                "123m".to_string(),
+                "456p".to_string(),
+                "789s".to_string(),
+                "EEEw".to_string(),
+                "11s".to_string(),
+            ],
+            "1s".to_string(),
+            "Ww".to_string(),
+            "Ww".to_string(),
+        )
+        .unwrap();
+        let ron = WinContext::builder().last_tile(true).build().unwrap();
+        let tsumo = ron.to_builder().tsumo(true).build().unwrap();
+
+        let score = get_hand_score(hand.clone(), &ron, &Ruleset::default()).unwrap();
+        assert_eq!(score.yaku(), [Yaku::Houtei]);
+
+        let score = get_hand_score(hand.clone(), &tsumo, &Ruleset::default()).unwrap();
+        assert_eq!(score.yaku(), [Yaku::Haitei, Yaku::MenzenTsumo]);
+
+        let score = get_hand_score(hand, &WinContext::default(), &Ruleset::default()).unwrap_err();
+        assert_eq!(score, HandErr::NoYaku);
+    }
+
+    #[test]
+    fn ruleset_kuitan() {
+        let open_tanyao = test_hand(&["234mo", "456p", "678s", "222s", "55m"], "5m", "Ew", "Ww");
+        let score = get_hand_score(
+            open_tanyao.clone(),
+            &WinContext::default(),
+            &Ruleset::default(),
+        )
+        .unwrap();
+        assert_eq!(score.yaku(), [Yaku::Tanyao]);
+
+        let rules = Ruleset {
+            kuitan: false,
+            ..Ruleset::default()
+        };
+        let out = get_hand_score(open_tanyao, &WinContext::default(), &rules).unwrap_err();
+        assert_eq!(out, HandErr::NoYaku);
+    }
+
+    #[test]
+    fn ruleset_double_yakuman() {
+        let kokushi = test_hand(
+            &[
+                "1p", "9p", "1s", "9s", "1m", "9m", "rd", "gd", "wd", "Ew", "Sw", "Nw", "WWw",
+            ],
+            "Ww",
+            "Ew",
+            "Ww",
+        );
+        let daisuushii = test_hand(&["EEEwo", "SSSw", "WWWw", "NNNw", "11m"], "1m", "Ew", "Ww");
+
+        for (hand, yaku) in [
+            (
+                kokushi,
+                vec![Yaku::KokushiMusou, Yaku::KokushiMusou13SidedWait],
+            ),
+            (daisuushii, vec![Yaku::Daisuushii]),
+        ] {
+            let score =
+                get_hand_score(hand.clone(), &WinContext::default(), &Ruleset::majsoul()).unwrap();
+            assert_eq!(score.yaku(), yaku);
+            assert_eq!(score.payment().base_points(), 16_000);
+            assert_eq!(score.limit_hand(), Some(LimitHands::Yakuman(2)));
+
+            let score = get_hand_score(hand, &WinContext::default(), &Ruleset::tenhou()).unwrap();
+            assert_eq!(score.payment().base_points(), 8_000);
+            assert_eq!(score.limit_hand(), Some(LimitHands::Yakuman(1)));
+        }
+    }
+
+    #[test]
+    fn ruleset_aka_dora_and_honba() {
+        let aka = test_hand(&["123m", "406p", "789s", "EEEw", "11s"], "1s", "Ew", "Ww");
+        let riichi = WinContext::builder().riichi(true).build().unwrap();
+
+        let score = get_hand_score(aka.clone(), &riichi, &Ruleset::tenhou()).unwrap();
+        assert_eq!(score.dora_count(), 1);
+        assert_eq!(score.dora().aka, 1);
+        let out = get_hand_score(aka, &riichi, &Ruleset::wrc()).unwrap_err();
+        assert_eq!(out, HandErr::TooManyAkaDora);
+
+        let payment = calculate(2, 30, &Ruleset::ema()).unwrap();
+        assert_eq!(payment.non_dealer_ron(3), payment.non_dealer_ron(0));
+        let payment = calculate(2, 30, &Ruleset::wrc()).unwrap();
+        assert_eq!(payment.non_dealer_ron(3), payment.non_dealer_ron(0) + 900);
+    }
+
+    #[test]
+    fn ruleset_aotenjou() {
+        let rules = Ruleset {
+            aotenjou: true,
+            ..Ruleset::default()
+        };
+        let payment = calculate(6, 30, &rules).unwrap();
+        assert_eq!(payment.base_points(), 30 * 2u64.pow(8));
+        assert_eq!(
+            calculate(60, 30, &rules).unwrap_err(),
+            HandErr::ScoreOverflow
+        );
+
+        // the han and fu fit, the honba on top don't
+        let payment = calculate(53, 30, &rules).unwrap();
+        assert!(payment.is_countable(0));
+        assert!(!payment.is_countable(40_000_000_000_000_000));
+        assert_eq!(payment.checked_dealer_ron(40_000_000_000_000_000), None);
+        let honba = WinContext::builder()
+            .honba(100_000_000_000_000_000)
+            .build()
+            .unwrap();
+        let hand = test_hand(&["123m", "456p", "789s", "EEEw", "11s"], "1s", "Ew", "Ww");
+        assert_eq!(
+            get_hand_score(hand, &honba, &rules).unwrap_err(),
+            HandErr::ScoreOverflow
+        );
+
+        // a double yakuman is 26 han, with menzen tsumo on top
+        let kokushi = test_hand(
+            &[
+                "1p", "9p", "1s", "9s", "1m", "9m", "rd", "gd", "wd", "Ew", "Sw", "Nw", "WWw",
+            ],
+            "Ww",
+            "Ew",
+            "Ww",
+        );
+        let tsumo = WinContext::builder().tsumo(true).build().unwrap();
+        let score = get_hand_score(kokushi, &tsumo, &rules).unwrap();
+        assert_eq!(
+            score.yaku(),
+            [
+                Yaku::KokushiMusou,
+                Yaku::KokushiMusou13SidedWait,
+                Yaku::MenzenTsumo
+            ]
+        );
+        assert_eq!(score.han(), 27);
+        assert_eq!(score.limit_hand(), None);
+    }
+
+    #[test]
+    fn ruleset_sanma() {
+        let rules = Ruleset::tenhou_sanma();
+        let hand = test_hand(&["111m", "456p", "789s", "NNNw", "99m"], "9m", "Ew", "Ww");
+        let context = WinContext::builder()
+            .tsumo(true)
+            .dora(vec!["1m".to_string().try_into().unwrap()])
+            .nukidora(1)
+            .build()
+            .unwrap();
+        let score = get_hand_score(hand, &context, &rules).unwrap();
+        // the 9m pair and the north tile set aside
+        assert_eq!(score.dora_count(), 3);
+        assert_eq!(
+            score.dora(),
+            Dora {
+                dora: 2,
+                aka: 0,
+                ura: 0,
+                nukidora: 1
+            }
+        );
+
+        let payment = calculate(3, 30, &rules).unwrap();
+        assert_eq!(payment.non_dealer_tsumo_to_dealer(1), 2_000 + 100);
+        assert_eq!(payment.non_dealer_ron(1), 3_900 + 200);
+
+        let payment = calculate(3, 30, &Ruleset::majsoul_sanma()).unwrap();
+        // base points of 960, the missing 960 (or 1920 from a dealer tsumo) is split in two
+        assert_eq!(payment.non_dealer_tsumo_to_dealer(0), 2_400);
+        assert_eq!(payment.non_dealer_tsumo_to_non_dealer(0), 1_500);
+        assert_eq!(payment.dealer_tsumo(0), 2_900);
+    }
+
+    #[test]
+    fn pao_payment() {
+        let call = |meld: &str, from: &str| CalledMeld {
+            meld: meld.to_string().try_into().unwrap(),
+            from: Some(from.to_string().try_into().unwrap()),
+        };
+        let daisangen = test_hand(
+            &["wwwdo", "gggdo", "rrrdo", "EEEw", "99p"],
+            "9p",
+            "Ew",
+            "Ww",
+        );
+        let context = WinContext::builder()
+            .called_from(vec![
+                call("wwwdo", "Sw"),
+                call("gggdo", "Ww"),
+                call("rrrdo", "Nw"),
+            ])
+            .build()
+            .unwrap();
+        let score = get_hand_score(daisangen.clone(), &context, &Ruleset::default()).unwrap();
+        assert_eq!(score.pao().unwrap().seat.to_string(), "Nw");
+        assert_eq!(score.payment().pao_base_points(), 8_000);
+
+        // only the daisangen is on the liable player, the tsuuiisou is paid as usual
+        let tsuuiisou = test_hand(
+            &["wwwdo", "gggdo", "rrrdo", "EEEw", "SSw"],
+            "Sw",
+            "Ew",
+            "Ww",
+        );
+        let score = get_hand_score(tsuuiisou, &context, &Ruleset::default()).unwrap();
+        assert_eq!(score.payment().base_points(), 16_000);
+        let tsumo = score.payment().pao(false, true, 0);
+        assert_eq!(tsumo.liable, 32_000);
+        assert_eq!(tsumo.dealer, 16_000);
+        assert_eq!(tsumo.non_dealer, 8_000);
+
+        let score = get_hand_score(daisangen, &WinContext::default(), &Ruleset::default()).unwrap();
+        assert_eq!(score.pao(), None);
+        assert_eq!(score.payment().pao_base_points(), 0);
+    }
+
+    #[test]
+    fn nagashi_mangan_score() {
+        let discards = crate::decompose::parse_tiles("19m19p19s1234567z").unwrap();
+        let context = WinContext::builder()
+            .honba(2)
+            .riichi_sticks(1)
+            .build()
+            .unwrap();
+
+        let draw = nagashi_mangan(&discards, false, &context, &Ruleset::tenhou()).unwrap();
+        assert_eq!(draw.limit_hand(), Some(LimitHands::Mangan));
+        assert_eq!(draw.yaku(), [Yaku::NagashiMangan]);
+        assert_eq!((draw.honba(), draw.riichi_sticks()), (0, 0));
+
+        let win_rules = Ruleset {
+            nagashi_mangan: Some(NagashiMangan::Win),
+            ..Ruleset::default()
+        };
+        let win = nagashi_mangan(&discards, false, &context, &win_rules).unwrap();
+        assert_eq!(win.payment().dealer_tsumo(win.honba()), 4_200);
+        assert_eq!(win.riichi_sticks(), 1);
+
+        assert_eq!(
+            nagashi_mangan(&discards, true, &context, &win_rules).unwrap_err(),
+            HandErr::NotNagashiMangan
+        );
+        assert_eq!(
+            nagashi_mangan(&discards, false, &context, &Ruleset::wrc()).unwrap_err(),
+            HandErr::NoNagashiMangan
+        );
+    }
 }
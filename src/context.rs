@@ -0,0 +1,486 @@
+use crate::hand::error::HandErr;
+use crate::hand::Hand;
+use crate::score::{HonbaCounter, RiichiStickCounter};
+use crate::suit::Suit;
+use crate::tile_group::{GroupType, TileGroup};
+
+/// A call made by the winner and the seat it was called from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalledMeld {
+    /// The called meld, open unless it is a closed kan.
+    pub meld: TileGroup,
+    /// Seat wind of the player the meld was called from, `None` for a closed kan.
+    pub from: Option<TileGroup>,
+}
+
+/// The situation a hand was won in.
+///
+/// Built with [`WinContextBuilder`], which rejects flags that cannot happen together.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::context::WinContext;
+/// use mahc::hand::error::HandErr;
+///
+/// let context = WinContext::builder().tsumo(true).riichi(true).ippatsu(true).build();
+/// assert!(context.is_ok());
+///
+/// let context = WinContext::builder().ippatsu(true).build();
+/// assert_eq!(context.unwrap_err(), HandErr::IppatsuWithoutRiichi);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WinContext {
+    tsumo: bool,
+    riichi: bool,
+    doubleriichi: bool,
+    ippatsu: bool,
+    last_tile: bool,
+    rinshan: bool,
+    chankan: bool,
+    tenhou: bool,
+    dora: Vec<TileGroup>,
+    ura_dora: Vec<TileGroup>,
+    nukidora: u32,
+    called_from: Vec<CalledMeld>,
+    honba: HonbaCounter,
+    riichi_sticks: RiichiStickCounter,
+}
+
+impl WinContext {
+    /// Start building a [`WinContext`], by default a ron with no other flags.
+    pub fn builder() -> WinContextBuilder {
+        WinContextBuilder::default()
+    }
+
+    /// Start a new builder from this context, to change some of its flags.
+    pub fn to_builder(&self) -> WinContextBuilder {
+        WinContextBuilder {
+            context: self.clone(),
+        }
+    }
+
+    /// Check the rules that depend on the hand that was won.
+    pub fn check_hand(&self, hand: &Hand) -> Result<(), HandErr> {
+        if (self.riichi || self.doubleriichi) && hand.is_open() {
+            return Err(HandErr::RiichiOpenHand);
+        }
+        if self.tenhou && (hand.is_open() || !hand.kans().is_empty()) {
+            return Err(HandErr::TenhouWithCalls);
+        }
+        if self.rinshan && hand.kans().is_empty() {
+            return Err(HandErr::RinshanKanWithoutKan);
+        }
+        // every kan turns over one more indicator, for both dora and ura dora
+        let indicators = 1 + hand.kans().len();
+        if self.dora.len() > indicators || self.ura_dora.len() > indicators {
+            return Err(HandErr::TooManyDoraIndicators);
+        }
+        Ok(())
+    }
+
+    /// Is the hand won by tsumo?
+    pub fn tsumo(&self) -> bool {
+        self.tsumo
+    }
+
+    /// Is riichi declared?
+    pub fn riichi(&self) -> bool {
+        self.riichi
+    }
+
+    /// Is double riichi declared?
+    pub fn doubleriichi(&self) -> bool {
+        self.doubleriichi
+    }
+
+    /// Is the hand won within a turn of riichi?
+    pub fn ippatsu(&self) -> bool {
+        self.ippatsu
+    }
+
+    /// Is the hand won on the last tile?
+    pub fn last_tile(&self) -> bool {
+        self.last_tile
+    }
+
+    /// Is the hand won by tsumo on the last tile of the wall (haitei raoyue)?
+    pub fn haitei(&self) -> bool {
+        self.last_tile && self.tsumo
+    }
+
+    /// Is the hand won by ron on the last discard (houtei raoyui)?
+    pub fn houtei(&self) -> bool {
+        self.last_tile && !self.tsumo
+    }
+
+    /// Is the hand won on the replacement tile after a kan?
+    pub fn rinshan(&self) -> bool {
+        self.rinshan
+    }
+
+    /// Is the hand won on a tile added to a kan?
+    pub fn chankan(&self) -> bool {
+        self.chankan
+    }
+
+    /// Is the hand won on the first draw (tenhou or chiihou)?
+    pub fn tenhou(&self) -> bool {
+        self.tenhou
+    }
+
+    /// Get the dora indicator tiles.
+    pub fn dora(&self) -> &[TileGroup] {
+        &self.dora
+    }
+
+    /// Get the ura dora indicator tiles.
+    pub fn ura_dora(&self) -> &[TileGroup] {
+        &self.ura_dora
+    }
+
+    /// Get the number of north tiles set aside as dora in sanma (nukidora).
+    pub fn nukidora(&self) -> u32 {
+        self.nukidora
+    }
+
+    /// Get the calls of the winner in the order they were made.
+    pub fn called_from(&self) -> &[CalledMeld] {
+        &self.called_from
+    }
+
+    /// Get the number of honba (repeat counters).
+    pub fn honba(&self) -> HonbaCounter {
+        self.honba
+    }
+
+    /// Get the number of riichi sticks (kyoutaku) on the table.
+    pub fn riichi_sticks(&self) -> RiichiStickCounter {
+        self.riichi_sticks
+    }
+}
+
+/// Builder for a [`WinContext`].
+#[derive(Debug, Default)]
+pub struct WinContextBuilder {
+    context: WinContext,
+}
+
+impl WinContextBuilder {
+    /// Set if the hand is won by tsumo.
+    pub fn tsumo(mut self, tsumo: bool) -> Self {
+        self.context.tsumo = tsumo;
+        self
+    }
+
+    /// Set if riichi is declared.
+    pub fn riichi(mut self, riichi: bool) -> Self {
+        self.context.riichi = riichi;
+        self
+    }
+
+    /// Set if double riichi is declared.
+    pub fn doubleriichi(mut self, doubleriichi: bool) -> Self {
+        self.context.doubleriichi = doubleriichi;
+        self
+    }
+
+    /// Set if the hand is won within a turn of riichi.
+    pub fn ippatsu(mut self, ippatsu: bool) -> Self {
+        self.context.ippatsu = ippatsu;
+        self
+    }
+
+    /// Set if the hand is won on the last tile, haitei by tsumo or houtei by ron.
+    pub fn last_tile(mut self, last_tile: bool) -> Self {
+        self.context.last_tile = last_tile;
+        self
+    }
+
+    /// Set if the hand is won on the replacement tile after a kan.
+    pub fn rinshan(mut self, rinshan: bool) -> Self {
+        self.context.rinshan = rinshan;
+        self
+    }
+
+    /// Set if the hand is won on a tile added to a kan.
+    pub fn chankan(mut self, chankan: bool) -> Self {
+        self.context.chankan = chankan;
+        self
+    }
+
+    /// Set if the hand is won on the first draw (tenhou or chiihou).
+    pub fn tenhou(mut self, tenhou: bool) -> Self {
+        self.context.tenhou = tenhou;
+        self
+    }
+
+    /// Set the dora indicator tiles.
+    pub fn dora(mut self, dora: Vec<TileGroup>) -> Self {
+        self.context.dora = dora;
+        self
+    }
+
+    /// Set the ura dora indicator tiles.
+    pub fn ura_dora(mut self, ura_dora: Vec<TileGroup>) -> Self {
+        self.context.ura_dora = ura_dora;
+        self
+    }
+
+    /// Set the number of north tiles set aside as dora in sanma (nukidora).
+    pub fn nukidora(mut self, nukidora: u32) -> Self {
+        self.context.nukidora = nukidora;
+        self
+    }
+
+    /// Set the calls of the winner in the order they were made, closed kans included so the order of kans is known.
+    pub fn called_from(mut self, called_from: Vec<CalledMeld>) -> Self {
+        self.context.called_from = called_from;
+        self
+    }
+
+    /// Set the number of honba (repeat counters).
+    pub fn honba(mut self, honba: HonbaCounter) -> Self {
+        self.context.honba = honba;
+        self
+    }
+
+    /// Set the number of riichi sticks (kyoutaku) on the table, including any put down by the winner.
+    pub fn riichi_sticks(mut self, riichi_sticks: RiichiStickCounter) -> Self {
+        self.context.riichi_sticks = riichi_sticks;
+        self
+    }
+
+    /// Check the flags go together and build the [`WinContext`].
+    pub fn build(self) -> Result<WinContext, HandErr> {
+        let c = self.context;
+        let riichi = c.riichi || c.doubleriichi;
+
+        if c.tsumo && c.chankan {
+            return Err(HandErr::ChankanTsumo);
+        }
+        if c.rinshan && !c.tsumo {
+            return Err(HandErr::RinshanWithoutTsumo);
+        }
+        if c.rinshan && c.ippatsu {
+            return Err(HandErr::RinshanIppatsu);
+        }
+        if c.rinshan && c.last_tile {
+            return Err(HandErr::HaiteiRinshan);
+        }
+        if c.last_tile && c.chankan {
+            return Err(HandErr::HouteiChankan);
+        }
+        if c.riichi && c.doubleriichi {
+            return Err(HandErr::DuplicateRiichi);
+        }
+        if c.ippatsu && !riichi {
+            return Err(HandErr::IppatsuWithoutRiichi);
+        }
+        if c.doubleriichi && c.ippatsu && c.last_tile {
+            return Err(HandErr::DoubleRiichiHaiteiIppatsu);
+        }
+        if !c.ura_dora.is_empty() && !riichi {
+            return Err(HandErr::UraDoraWithoutRiichi);
+        }
+        if c.tenhou && !c.tsumo {
+            return Err(HandErr::TenhouWithoutTsumo);
+        }
+        if c.tenhou && riichi {
+            return Err(HandErr::TenhouRiichi);
+        }
+        for call in &c.called_from {
+            let valid = match &call.from {
+                Some(seat) => call.meld.isopen && seat.suit == Suit::Wind,
+                None => !call.meld.isopen && call.meld.group_type == GroupType::Kan,
+            };
+            if !valid {
+                return Err(HandErr::InvalidGroup);
+            }
+        }
+
+        Ok(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand::test_hand;
+
+    #[test]
+    fn invalid_flag_combinations() {
+        let cases = [
+            (
+                WinContext::builder().tsumo(true).chankan(true),
+                HandErr::ChankanTsumo,
+            ),
+            (
+                WinContext::builder().rinshan(true),
+                HandErr::RinshanWithoutTsumo,
+            ),
+            (
+                WinContext::builder()
+                    .tsumo(true)
+                    .riichi(true)
+                    .ippatsu(true)
+                    .rinshan(true),
+                HandErr::RinshanIppatsu,
+            ),
+            (
+                WinContext::builder()
+                    .tsumo(true)
+                    .last_tile(true)
+                    .rinshan(true),
+                HandErr::HaiteiRinshan,
+            ),
+            (
+                WinContext::builder().last_tile(true).chankan(true),
+                HandErr::HouteiChankan,
+            ),
+            (
+                WinContext::builder().riichi(true).doubleriichi(true),
+                HandErr::DuplicateRiichi,
+            ),
+            (
+                WinContext::builder().ippatsu(true),
+                HandErr::IppatsuWithoutRiichi,
+            ),
+            (
+                WinContext::builder().ura_dora(vec!["1m".to_string().try_into().unwrap()]),
+                HandErr::UraDoraWithoutRiichi,
+            ),
+            (
+                WinContext::builder().tenhou(true),
+                HandErr::TenhouWithoutTsumo,
+            ),
+            (
+                WinContext::builder().tsumo(true).tenhou(true).riichi(true),
+                HandErr::TenhouRiichi,
+            ),
+            (
+                WinContext::builder()
+                    .tsumo(true)
+                    .tenhou(true)
+                    .doubleriichi(true),
+                HandErr::TenhouRiichi,
+            ),
+        ];
+        for (builder, err) in cases {
+            assert_eq!(builder.build().unwrap_err(), err);
+        }
+
+        let call = |meld: &str, from: Option<&str>| CalledMeld {
+            meld: meld.to_string().try_into().unwrap(),
+            from: from.map(|seat| seat.to_string().try_into().unwrap()),
+        };
+        for called_from in [
+            vec![call("rrrd", Some("Sw"))],
+            vec![call("rrrdo", Some("5m"))],
+            vec![call("rrrdo", None)],
+        ] {
+            let out = WinContext::builder().called_from(called_from).build();
+            assert_eq!(out.unwrap_err(), HandErr::InvalidGroup);
+        }
+        let called_from = vec![call("rrrdo", Some("Sw")), call("1111m", None)];
+        let context = WinContext::builder()
+            .called_from(called_from.clone())
+            .build()
+            .unwrap();
+        assert_eq!(context.called_from(), called_from);
+
+        let context = WinContext::builder()
+            .doubleriichi(true)
+            .ura_dora(vec!["1m".to_string().try_into().unwrap()])
+            .build()
+            .unwrap();
+        assert_eq!(context.ura_dora().len(), 1);
+    }
+
+    #[test]
+    fn invalid_for_hand() {
+        let open = test_hand(&["123mo", "456p", "789s", "EEEw", "11s"], "1s", "Ew", "Ew");
+        let closed_kan = test_hand(&["123m", "456p", "789s", "EEEEw", "11s"], "1s", "Ew", "Ew");
+
+        let riichi = WinContext::builder().riichi(true).build().unwrap();
+        assert_eq!(riichi.check_hand(&open), Err(HandErr::RiichiOpenHand));
+        assert_eq!(riichi.check_hand(&closed_kan), Ok(()));
+
+        let tenhou = WinContext::builder()
+            .tsumo(true)
+            .tenhou(true)
+            .build()
+            .unwrap();
+        assert_eq!(tenhou.check_hand(&open), Err(HandErr::TenhouWithCalls));
+        assert_eq!(
+            tenhou.check_hand(&closed_kan),
+            Err(HandErr::TenhouWithCalls)
+        );
+
+        let rinshan = WinContext::builder()
+            .tsumo(true)
+            .rinshan(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            rinshan.check_hand(&open),
+            Err(HandErr::RinshanKanWithoutKan)
+        );
+        assert_eq!(rinshan.check_hand(&closed_kan), Ok(()));
+
+        let tiles = |tiles: &[&str]| -> Vec<TileGroup> {
+            tiles
+                .iter()
+                .map(|tile| tile.to_string().try_into().unwrap())
+                .collect()
+        };
+        let kan_dora = WinContext::builder()
+            .riichi(true)
+            .dora(tiles(&["1m", "2m"]))
+            .ura_dora(tiles(&["3m", "4m"]))
+            .build()
+            .unwrap();
+        let closed = test_hand(&["123m", "456p", "789s", "EEEw", "11s"], "1s", "Ew", "Ew");
+        assert_eq!(kan_dora.check_hand(&closed_kan), Ok(()));
+        assert_eq!(
+            kan_dora.check_hand(&closed),
+            Err(HandErr::TooManyDoraIndicators)
+        );
+        let ura_kan_dora = kan_dora.to_builder().dora(tiles(&["1m"])).build().unwrap();
+        assert_eq!(
+            ura_kan_dora.check_hand(&closed),
+            Err(HandErr::TooManyDoraIndicators)
+        );
+    }
+
+    #[test]
+    fn last_tile_by_win_method() {
+        let houtei = WinContext::builder().last_tile(true).build().unwrap();
+        assert!(houtei.houtei());
+        assert!(!houtei.haitei());
+
+        let haitei = houtei.to_builder().tsumo(true).build().unwrap();
+        assert!(haitei.haitei());
+        assert!(!haitei.houtei());
+
+        let neither = WinContext::builder().tsumo(true).build().unwrap();
+        assert!(!neither.haitei());
+        assert!(!neither.houtei());
+    }
+
+    #[test]
+    fn change_context() {
+        let ron = WinContext::builder()
+            .riichi(true)
+            .honba(2)
+            .riichi_sticks(1)
+            .build()
+            .unwrap();
+        let tsumo = ron.to_builder().tsumo(true).build().unwrap();
+        assert!(!ron.tsumo());
+        assert!(tsumo.tsumo());
+        assert!(tsumo.riichi());
+        assert_eq!(tsumo.honba(), 2);
+        assert_eq!(tsumo.riichi_sticks(), 1);
+    }
+}
@@ -0,0 +1,405 @@
+use crate::hand::error::HandErr;
+use crate::hand::Hand;
+use crate::tile_group::{tile_counts, GroupType, TileGroup, TILE_KINDS};
+
+/// Tile kind indices of the thirteen terminal and honor tiles needed for kokushi musou.
+pub const ORPHAN_INDICES: [usize; 13] = [0, 8, 9, 17, 18, 26, 27, 28, 29, 30, 31, 32, 33];
+
+/// Parse an ungrouped string of tiles (ex: `123456m789p11z55z`) into single tiles.
+///
+/// Both the MPSZ notation and the honor letters (ex: `EESw`, `rrgd`) are accepted.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::decompose::parse_tiles;
+///
+/// let tiles = parse_tiles("406sEEw7z").unwrap();
+/// let actual: Vec<String> = tiles.iter().map(|tile| tile.to_string()).collect();
+/// assert_eq!(actual, ["4s", "0s", "6s", "Ew", "Ew", "rd"]);
+/// ```
+pub fn parse_tiles(tiles: &str) -> Result<Vec<TileGroup>, HandErr> {
+    let mut parsed: Vec<TileGroup> = vec![];
+    let mut values: Vec<char> = vec![];
+
+    for c in tiles.chars().filter(|c| !c.is_whitespace()) {
+        let is_suit = match c {
+            'm' | 'p' | 's' | 'z' | 'd' => true,
+            // `w` is both the wind suit and the white dragon, it can only be a suit after wind values.
+            'w' => !values.is_empty() && values.iter().all(|v| "ESWN".contains(*v)),
+            _ => false,
+        };
+
+        if !is_suit {
+            values.push(c);
+            continue;
+        }
+        if values.is_empty() {
+            return Err(HandErr::InvalidGroup);
+        }
+        for value in values.drain(..) {
+            parsed.push(format!("{value}{c}").try_into()?);
+        }
+    }
+
+    if !values.is_empty() {
+        return Err(HandErr::InvalidSuit);
+    }
+
+    Ok(parsed)
+}
+
+/// Find every arrangement of the hand into four groups and a pair, seven pairs or kokushi musou.
+///
+/// `closed` holds the concealed tiles, with or without the winning tile, and `calls` holds the called melds (and closed kans).
+/// Each decomposition lists the called melds first, followed by the concealed groups in tile order.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::decompose::{decompose, parse_tiles};
+///
+/// let closed = parse_tiles("222333444m5599p").unwrap();
+/// let win_tile = "9p".to_string().try_into().unwrap();
+/// let decompositions = decompose(&closed, &[], &win_tile).unwrap();
+///
+/// // three triplets or three identical sequences
+/// assert_eq!(decompositions.len(), 2);
+/// ```
+pub fn decompose(
+    closed: &[TileGroup],
+    calls: &[TileGroup],
+    win_tile: &TileGroup,
+) -> Result<Vec<Vec<TileGroup>>, HandErr> {
+    for call in calls {
+        if matches!(call.group_type, GroupType::Pair | GroupType::None) {
+            return Err(HandErr::InvalidGroup);
+        }
+    }
+    if calls.len() > 4 {
+        return Err(HandErr::InvalidShape);
+    }
+
+    let mut counts = tile_counts(closed);
+    let mut aka: Vec<usize> = closed
+        .iter()
+        .filter(|tile| tile.isaka)
+        .map(|tile| tile.tile_index() + 5 - tile.parse_u8().unwrap() as usize)
+        .collect();
+
+    let win_index = win_tile.tile_index();
+    match counts.iter().sum::<u8>() as usize + calls.len() * 3 {
+        13 => {
+            counts[win_index] += 1;
+            if win_tile.isaka {
+                aka.push(win_index);
+            }
+        }
+        14 => {
+            if counts[win_index] == 0 {
+                return Err(HandErr::InvalidShape);
+            }
+        }
+        _ => return Err(HandErr::InvalidShape),
+    }
+
+    let all_counts = tile_counts(calls)
+        .iter()
+        .zip(counts.iter())
+        .map(|(called, concealed)| called + concealed)
+        .collect::<Vec<u8>>();
+    if all_counts.iter().any(|&count| count > 4) {
+        return Err(HandErr::InvalidShape);
+    }
+
+    let mut shapes: Vec<Vec<(usize, GroupType)>> = vec![];
+    let sets_needed = 4 - calls.len();
+    for pair in 0..TILE_KINDS {
+        if counts[pair] < 2 {
+            continue;
+        }
+        counts[pair] -= 2;
+        let mut current = vec![(pair, GroupType::Pair)];
+        find_sets(&mut counts, sets_needed, &mut current, &mut shapes);
+        counts[pair] += 2;
+    }
+
+    if calls.is_empty() {
+        if counts.iter().filter(|&&count| count == 2).count() == 7 {
+            shapes.push(
+                (0..TILE_KINDS)
+                    .filter(|&index| counts[index] == 2)
+                    .map(|index| (index, GroupType::Pair))
+                    .collect(),
+            );
+        }
+
+        if ORPHAN_INDICES.iter().all(|&index| counts[index] > 0)
+            && ORPHAN_INDICES
+                .iter()
+                .map(|&index| counts[index])
+                .sum::<u8>()
+                == 14
+        {
+            shapes.push(
+                ORPHAN_INDICES
+                    .iter()
+                    .map(|&index| {
+                        if counts[index] == 2 {
+                            (index, GroupType::Pair)
+                        } else {
+                            (index, GroupType::None)
+                        }
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    if shapes.is_empty() {
+        return Err(HandErr::InvalidShape);
+    }
+
+    let mut decompositions = vec![];
+    for shape in shapes {
+        let mut groups: Vec<TileGroup> = calls.to_vec();
+        let mut concealed = vec![];
+        for (index, group_type) in shape {
+            concealed.push(TileGroup::from_index(index, group_type)?);
+        }
+        concealed.sort_by_key(|group| group.tile_index());
+
+        // hand the red fives out to the groups holding a five of the same suit
+        for &five in &aka {
+            if let Some(group) = concealed
+                .iter_mut()
+                .find(|group| !group.isaka && group.tile_indices().contains(&five))
+            {
+                group.isaka = true;
+            }
+        }
+
+        groups.append(&mut concealed);
+        decompositions.push(groups);
+    }
+
+    Ok(decompositions)
+}
+
+/// Remove complete sets from the counts, always starting from the lowest remaining tile so no arrangement is found twice.
+fn find_sets(
+    counts: &mut [u8; TILE_KINDS],
+    sets_needed: usize,
+    current: &mut Vec<(usize, GroupType)>,
+    shapes: &mut Vec<Vec<(usize, GroupType)>>,
+) {
+    let Some(index) = counts.iter().position(|&count| count > 0) else {
+        if sets_needed == 0 {
+            shapes.push(current.clone());
+        }
+        return;
+    };
+    if sets_needed == 0 {
+        return;
+    }
+
+    if counts[index] >= 3 {
+        counts[index] -= 3;
+        current.push((index, GroupType::Triplet));
+        find_sets(counts, sets_needed - 1, current, shapes);
+        current.pop();
+        counts[index] += 3;
+    }
+
+    if index < 27 && index % 9 <= 6 && counts[index + 1] > 0 && counts[index + 2] > 0 {
+        counts[index..index + 3]
+            .iter_mut()
+            .for_each(|count| *count -= 1);
+        current.push((index, GroupType::Sequence));
+        find_sets(counts, sets_needed - 1, current, shapes);
+        current.pop();
+        counts[index..index + 3]
+            .iter_mut()
+            .for_each(|count| *count += 1);
+    }
+}
+
+/// Build a [`Hand`] from an ungrouped string of concealed tiles for every decomposition found.
+///
+/// Each hand lists every wait the winning tile could have completed through [`Hand::waits`].
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::decompose::decompose_hand;
+///
+/// let hands = decompose_hand("123456m789p11z55z", vec![], "5z".to_string(), "Ew".to_string(), "Ew".to_string()).unwrap();
+/// assert_eq!(hands.len(), 1);
+/// assert_eq!(hands[0].pairs()[0].to_string(), "EEw");
+///
+/// // the 3m either completes the 123m or the 345m
+/// let hands = decompose_hand("12345m456p789s11z", vec![], "3m".to_string(), "Ew".to_string(), "Ew".to_string()).unwrap();
+/// assert_eq!(hands.len(), 1);
+/// assert_eq!(hands[0].waits().len(), 2);
+/// ```
+pub fn decompose_hand(
+    tiles: &str,
+    calls: Vec<String>,
+    win: String,
+    prev: String,
+    seat: String,
+) -> Result<Vec<Hand>, HandErr> {
+    let closed = parse_tiles(tiles)?;
+    let mut called: Vec<TileGroup> = vec![];
+    for call in calls {
+        called.push(call.try_into()?);
+    }
+    let win_tile: TileGroup = win.try_into()?;
+    let seat_tile: TileGroup = seat.try_into()?;
+    let prev_tile: TileGroup = prev.try_into()?;
+
+    let mut hands = vec![];
+    for groups in decompose(&closed, &called, &win_tile)? {
+        hands.push(Hand::new(
+            groups,
+            win_tile.clone(),
+            seat_tile.clone(),
+            prev_tile.clone(),
+        )?);
+    }
+
+    Ok(hands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups_to_strings(groups: &[TileGroup]) -> Vec<String> {
+        groups.iter().map(|group| group.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_mixed_notation() {
+        let tiles = parse_tiles("19m19p19sESWNwwgrd1m").unwrap();
+        assert_eq!(tiles.len(), 14);
+        assert_eq!(tiles[10].to_string(), "wd");
+
+        assert_eq!(parse_tiles("123"), Err(HandErr::InvalidSuit));
+        assert_eq!(parse_tiles("m123p"), Err(HandErr::InvalidGroup));
+        assert_eq!(parse_tiles("8z"), Err(HandErr::InvalidGroup));
+    }
+
+    #[test]
+    fn decompose_standard_hand() {
+        let closed = parse_tiles("123456m789p11z55z").unwrap();
+        let win_tile: TileGroup = "5z".to_string().try_into().unwrap();
+        let decompositions = decompose(&closed, &[], &win_tile).unwrap();
+        assert_eq!(decompositions.len(), 1);
+        assert_eq!(
+            groups_to_strings(&decompositions[0]),
+            ["123m", "456m", "789p", "EEw", "wwwd"]
+        );
+    }
+
+    #[test]
+    fn decompose_with_calls() {
+        let closed = parse_tiles("234s067p9p").unwrap();
+        let calls: Vec<TileGroup> = vec![
+            "rrrdo".to_string().try_into().unwrap(),
+            "1111m".to_string().try_into().unwrap(),
+        ];
+        let win_tile: TileGroup = "9p".to_string().try_into().unwrap();
+        let decompositions = decompose(&closed, &calls, &win_tile).unwrap();
+        assert_eq!(decompositions.len(), 1);
+        assert_eq!(
+            groups_to_strings(&decompositions[0]),
+            ["rrrdo", "1111m", "067p", "99p", "234s"]
+        );
+    }
+
+    #[test]
+    fn decompose_many_shapes() {
+        let closed = parse_tiles("22334455667788s").unwrap();
+        let win_tile: TileGroup = "8s".to_string().try_into().unwrap();
+        let decompositions = decompose(&closed, &[], &win_tile).unwrap();
+        let shapes: Vec<Vec<String>> = decompositions
+            .iter()
+            .map(|groups| groups_to_strings(groups))
+            .collect();
+
+        assert!(shapes.contains(&vec![
+            "22s".to_string(),
+            "33s".to_string(),
+            "44s".to_string(),
+            "55s".to_string(),
+            "66s".to_string(),
+            "77s".to_string(),
+            "88s".to_string(),
+        ]));
+        assert!(shapes.contains(&vec![
+            "22s".to_string(),
+            "345s".to_string(),
+            "345s".to_string(),
+            "678s".to_string(),
+            "678s".to_string(),
+        ]));
+        assert!(shapes.contains(&vec![
+            "234s".to_string(),
+            "234s".to_string(),
+            "55s".to_string(),
+            "678s".to_string(),
+            "678s".to_string(),
+        ]));
+    }
+
+    #[test]
+    fn decompose_kokushi() {
+        let closed = parse_tiles("19m19p19s1234567z").unwrap();
+        let win_tile: TileGroup = "1z".to_string().try_into().unwrap();
+        let decompositions = decompose(&closed, &[], &win_tile).unwrap();
+        assert_eq!(decompositions.len(), 1);
+        assert_eq!(decompositions[0].len(), 13);
+        assert_eq!(decompositions[0][6].to_string(), "EEw");
+    }
+
+    #[test]
+    fn decompose_invalid() {
+        let win_tile: TileGroup = "1z".to_string().try_into().unwrap();
+
+        let closed = parse_tiles("1234567m1234567p").unwrap();
+        assert_eq!(
+            decompose(&closed, &[], &win_tile),
+            Err(HandErr::InvalidShape)
+        );
+
+        let closed = parse_tiles("11111m234p567s99p").unwrap();
+        assert_eq!(
+            decompose(&closed, &[], &win_tile),
+            Err(HandErr::InvalidShape)
+        );
+
+        let closed = parse_tiles("123m456p789s11z22z").unwrap();
+        let win_tile: TileGroup = "3z".to_string().try_into().unwrap();
+        assert_eq!(
+            decompose(&closed, &[], &win_tile),
+            Err(HandErr::InvalidShape)
+        );
+    }
+
+    #[test]
+    fn decompose_hand_with_calls() {
+        let hands = decompose_hand(
+            "234m456p11s789s",
+            vec!["rrrdo".to_string()],
+            "4p".to_string(),
+            "Ew".to_string(),
+            "Ew".to_string(),
+        )
+        .unwrap();
+        assert_eq!(hands.len(), 1);
+        assert!(hands[0].is_open());
+        let wait = hands[0].wait();
+        assert_eq!(hands[0].groups()[wait.group].to_string(), "456p");
+    }
+}
@@ -62,8 +62,13 @@ impl Fu {
 }
 
 /// Sum up all of the fu, rounding to the nearest 10.
+///
+/// Chiitoitsu (seven pairs) is always 25 fu and is not rounded.
 pub fn calculate_total_fu_value(fu: &[Fu]) -> FuValue {
-    ((fu.iter().map(|f| f.value()).sum::<FuValue>() + 9) / 10) * 10
+    if fu.contains(&Fu::BasePointsChitoi) {
+        return Fu::BasePointsChitoi.value();
+    }
+    fu.iter().map(|f| f.value()).sum::<FuValue>().div_ceil(10) * 10
 }
 
 #[cfg(test)]
@@ -101,6 +106,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fu_calc_closed_tsumo_no_ron_fu() {
+        let out = Hand::new_from_strings(
+            vec![
+                "234m".to_string(),
+                "456p".to_string(),
+                "789s".to_string(),
+                "EEw".to_string(),
+                "NNNw".to_string(),
+            ],
+            "Nw".to_string(),
+            "Sw".to_string(),
+            "Ew".to_string(),
+        )
+        .unwrap();
+        let actual_fu = out.calculate_fu(true);
+        assert_eq!(calculate_total_fu_value(&actual_fu), 40);
+        assert_eq!(
+            actual_fu,
+            [
+                Fu::BasePoints,
+                Fu::Tsumo,
+                Fu::NonSimpleClosedTriplet,
+                Fu::Toitsu,
+            ]
+        );
+    }
+
+    #[test]
+    fn fu_calc_chiitoitsu_not_rounded() {
+        assert_eq!(calculate_total_fu_value(&[Fu::BasePointsChitoi]), 25);
+    }
+
     #[test]
     fn fu_calc_edge_wait() {
         let out = Hand::new_from_strings(
@@ -130,6 +168,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fu_calc_wait_group_not_last() {
+        let out = Hand::new_from_strings(
+            vec![
+                "789m".to_string(),
+                "555po".to_string(),
+                "234m".to_string(),
+                "11s".to_string(),
+                "rrrdo".to_string(),
+            ],
+            "7m".to_string(),
+            "Ew".to_string(),
+            "Ww".to_string(),
+        )
+        .unwrap();
+        let actual_fu = out.calculate_fu(true);
+        assert_eq!(calculate_total_fu_value(&actual_fu), 30);
+        assert_eq!(
+            actual_fu,
+            [
+                Fu::BasePoints,
+                Fu::Tsumo,
+                Fu::SimpleOpenTriplet,
+                Fu::NonSimpleOpenTriplet,
+                Fu::SingleWait,
+            ]
+        );
+    }
+
     #[test]
     fn random_fu() {
         let out = Hand::new_from_strings(
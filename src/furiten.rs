@@ -0,0 +1,193 @@
+use crate::hand::error::HandErr;
+use crate::hand::Hand;
+use crate::tenpai::wait_tiles;
+use crate::tile_group::{GroupType, TileGroup};
+
+/// The reason a tenpai hand cannot win by ron.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FuritenType {
+    /// A wait is in the player's own discards, lasting until the waits change.
+    Permanent,
+    /// A wait was passed since the player's last turn, lasting until their next discard.
+    Temporary,
+    /// A wait was passed after declaring riichi, lasting until the end of the hand.
+    Riichi,
+}
+
+impl std::fmt::Display for FuritenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Permanent => write!(f, "Permanent"),
+            Self::Temporary => write!(f, "Temporary"),
+            Self::Riichi => write!(f, "Riichi"),
+        }
+    }
+}
+
+/// A kind of furiten the hand is in and the waits causing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Furiten {
+    /// The kind of furiten.
+    pub furiten_type: FuritenType,
+    /// The waits that were discarded or passed.
+    pub waits: Vec<TileGroup>,
+}
+
+/// Check the waits of a hand against the player's discards and the tiles they passed on.
+///
+/// `passed` holds the tiles let go since riichi was declared when `riichi` is set, or since the player's last turn otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::furiten::{check_furiten, FuritenType};
+/// use mahc::decompose::parse_tiles;
+///
+/// let waits = parse_tiles("1m4m").unwrap();
+/// let discards = parse_tiles("9p4m").unwrap();
+/// let furiten = check_furiten(&waits, &discards, &[], false);
+/// assert_eq!(furiten[0].furiten_type, FuritenType::Permanent);
+/// assert_eq!(furiten[0].waits[0].to_string(), "4m");
+/// ```
+pub fn check_furiten(
+    waits: &[TileGroup],
+    discards: &[TileGroup],
+    passed: &[TileGroup],
+    riichi: bool,
+) -> Vec<Furiten> {
+    let matching = |tiles: &[TileGroup]| -> Vec<TileGroup> {
+        waits
+            .iter()
+            .filter(|wait| {
+                tiles
+                    .iter()
+                    .any(|tile| tile.tile_index() == wait.tile_index())
+            })
+            .cloned()
+            .collect()
+    };
+    let passed_type = if riichi {
+        FuritenType::Riichi
+    } else {
+        FuritenType::Temporary
+    };
+
+    [
+        (FuritenType::Permanent, matching(discards)),
+        (passed_type, matching(passed)),
+    ]
+    .into_iter()
+    .filter(|(_, waits)| !waits.is_empty())
+    .map(|(furiten_type, waits)| Furiten {
+        furiten_type,
+        waits,
+    })
+    .collect()
+}
+
+/// Check if a complete hand was furiten before winning on its winning tile.
+///
+/// The waits are those of the hand without the winning tile.
+pub fn hand_furiten(
+    hand: &Hand,
+    discards: &[TileGroup],
+    passed: &[TileGroup],
+    riichi: bool,
+) -> Result<Vec<Furiten>, HandErr> {
+    let (calls, concealed): (Vec<TileGroup>, Vec<TileGroup>) = hand
+        .groups()
+        .iter()
+        .cloned()
+        .partition(|group| group.isopen || group.group_type == GroupType::Kan);
+
+    let mut closed = vec![];
+    for index in concealed.iter().flat_map(|group| group.tile_indices()) {
+        closed.push(TileGroup::from_index(index, GroupType::None)?);
+    }
+    let win_index = hand.win_tile().tile_index();
+    let Some(win_position) = closed
+        .iter()
+        .position(|tile| tile.tile_index() == win_index)
+    else {
+        return Err(HandErr::InvalidShape);
+    };
+    closed.remove(win_position);
+
+    Ok(check_furiten(
+        &wait_tiles(&closed, &calls)?,
+        discards,
+        passed,
+        riichi,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decompose::{decompose_hand, parse_tiles};
+
+    fn tiles(tiles: &str) -> Vec<TileGroup> {
+        parse_tiles(tiles).unwrap()
+    }
+
+    fn furiten_waits(furiten: &Furiten) -> Vec<String> {
+        furiten.waits.iter().map(|tile| tile.to_string()).collect()
+    }
+
+    #[test]
+    fn furiten_types() {
+        let waits = tiles("2m5m8m");
+        assert!(check_furiten(&waits, &tiles("1m9p"), &tiles("3m"), false).is_empty());
+
+        let out = check_furiten(&waits, &tiles("8m9p"), &tiles("2m5m"), false);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].furiten_type, FuritenType::Permanent);
+        assert_eq!(furiten_waits(&out[0]), vec!["8m"]);
+        assert_eq!(out[1].furiten_type, FuritenType::Temporary);
+        assert_eq!(furiten_waits(&out[1]), vec!["2m", "5m"]);
+
+        let out = check_furiten(&waits, &[], &tiles("0m"), true);
+        assert_eq!(out[0].furiten_type, FuritenType::Riichi);
+        assert_eq!(furiten_waits(&out[0]), vec!["5m"]);
+    }
+
+    #[test]
+    fn furiten_on_other_wait() {
+        // winning on 5m while 8m is in the discards is still furiten
+        let hand = decompose_hand(
+            "2345678m456p789s5m",
+            vec![],
+            "5m".to_string(),
+            "Ew".to_string(),
+            "Ew".to_string(),
+        )
+        .unwrap()
+        .remove(0);
+        let out = hand_furiten(&hand, &tiles("8m"), &[], false).unwrap();
+        assert_eq!(out[0].furiten_type, FuritenType::Permanent);
+        assert_eq!(furiten_waits(&out[0]), vec!["8m"]);
+
+        let out = hand_furiten(&hand, &tiles("1m"), &[], false).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn furiten_with_calls() {
+        let hand = Hand::new_from_strings(
+            vec![
+                "rrrdo".to_string(),
+                "1111p".to_string(),
+                "456p".to_string(),
+                "789s".to_string(),
+                "99m".to_string(),
+            ],
+            "9m".to_string(),
+            "Ew".to_string(),
+            "Ew".to_string(),
+        )
+        .unwrap();
+        let out = hand_furiten(&hand, &[], &tiles("9m"), true).unwrap();
+        assert_eq!(out[0].furiten_type, FuritenType::Riichi);
+        assert_eq!(furiten_waits(&out[0]), vec!["9m"]);
+    }
+}
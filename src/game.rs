@@ -0,0 +1,598 @@
+use crate::calc::{get_hand_score, nagashi_mangan};
+use crate::context::{WinContext, WinContextBuilder};
+use crate::hand::error::HandErr;
+use crate::hand::Hand;
+use crate::payment::RIICHI_DEPOSIT;
+use crate::ruleset::{NagashiMangan, Ruleset};
+use crate::ryuukyoku::exhaustive_draw;
+use crate::score::{HonbaCounter, RiichiStickCounter};
+use crate::settlement::{
+    add_delta, multiple_ron, seat_index, settle, to_delta, MultipleRon, PointDelta, SEATS,
+};
+use crate::tile_group::TileGroup;
+
+/// How many winds a game is played over.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameLength {
+    /// East round only.
+    Tonpuusen,
+    /// East and south rounds.
+    Hanchan,
+}
+
+impl GameLength {
+    /// Get the number of round winds played.
+    pub fn winds(&self) -> usize {
+        match self {
+            Self::Tonpuusen => 1,
+            Self::Hanchan => 2,
+        }
+    }
+}
+
+/// The rules of a whole game, on top of the [`Ruleset`] its hands are scored with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRules {
+    /// Number of winds played.
+    pub length: GameLength,
+    /// Points every player starts with.
+    pub starting_points: PointDelta,
+    /// The game ends when a player goes below zero points (tobi).
+    pub tobi: bool,
+    /// The game ends when the dealer wins the last round in first place (agari-yame).
+    pub agari_yame: bool,
+    /// The game ends when the dealer is tenpai at a draw of the last round in first place (tenpai-yame).
+    pub tenpai_yame: bool,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            length: GameLength::Hanchan,
+            starting_points: 25_000,
+            tobi: true,
+            agari_yame: false,
+            tenpai_yame: false,
+        }
+    }
+}
+
+/// Why a game ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameEnd {
+    /// A player went below zero points.
+    Tobi,
+    /// The last round was played out.
+    LastRound,
+    /// The dealer won the last round in first place.
+    AgariYame,
+    /// The dealer was tenpai at a draw of the last round in first place.
+    TenpaiYame,
+}
+
+impl std::fmt::Display for GameEnd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tobi => write!(f, "Tobi"),
+            Self::LastRound => write!(f, "Last Round"),
+            Self::AgariYame => write!(f, "Agari-yame"),
+            Self::TenpaiYame => write!(f, "Tenpai-yame"),
+        }
+    }
+}
+
+/// How a hand of the game ended.
+#[derive(Debug, Clone)]
+pub enum HandOutcome {
+    /// A player won by tsumo, the hand's seat wind is the winner.
+    Tsumo(Hand, WinContext),
+    /// One or more players won by ron off the discard of a seat.
+    Ron {
+        /// Each winner's hand and context.
+        wins: Vec<(Hand, WinContext)>,
+        /// Seat wind of the player who dealt in.
+        discarder: TileGroup,
+    },
+    /// The wall ran out, with the seat winds of the players who were tenpai.
+    ExhaustiveDraw {
+        /// Seat winds of the tenpai players.
+        tenpai: Vec<TileGroup>,
+    },
+    /// The hand was called off (kyuushu kyuuhai, suufon renda and the like).
+    AbortiveDraw,
+    /// The wall ran out and a player had nagashi mangan.
+    NagashiMangan {
+        /// Seat wind of the player with nagashi mangan.
+        seat: TileGroup,
+        /// Discards of the player.
+        discards: Vec<TileGroup>,
+        /// Another player called one of the discards.
+        called: bool,
+        /// Seat winds of the tenpai players, for when nagashi mangan is a draw.
+        tenpai: Vec<TileGroup>,
+    },
+}
+
+/// The state of a game being played, kept between hands.
+///
+/// Players are numbered from the first dealer, in turn order. The honba and riichi sticks of the game are used in
+/// place of the ones in each win's context, and a winner who declared riichi wins with riichi.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::context::WinContext;
+/// use mahc::game::{Game, GameRules, HandOutcome};
+/// use mahc::hand::Hand;
+/// use mahc::ruleset::Ruleset;
+///
+/// let mut game = Game::new(GameRules::default(), Ruleset::default());
+/// let south = "Sw".to_string().try_into().unwrap();
+/// game.declare_riichi(&south).unwrap();
+///
+/// let hand = Hand::new_from_strings(
+///     vec!["123m".to_string(), "456p".to_string(), "789s".to_string(), "SSSw".to_string(), "11s".to_string()],
+///     "1s".to_string(),
+///     "Ew".to_string(),
+///     "Sw".to_string(),
+/// )
+/// .unwrap();
+/// let context = WinContext::builder().riichi(true).build().unwrap();
+/// let outcome = HandOutcome::Ron { wins: vec![(hand, context)], discarder: "Nw".to_string().try_into().unwrap() };
+///
+/// let deltas = game.record(outcome).unwrap();
+/// assert_eq!(deltas, [0, 2_600 + 1_000, 0, -2_600]);
+/// assert_eq!(game.points, [25_000, 27_600, 25_000, 22_400]);
+/// assert_eq!(game.dealer(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    /// The rules of the game.
+    pub game_rules: GameRules,
+    /// The ruleset hands are scored with.
+    pub rules: Ruleset,
+    /// Points of each player.
+    pub points: Vec<PointDelta>,
+    /// Number of times the dealer has passed, 0 is east 1.
+    pub round: usize,
+    /// Number of honba (repeat counters).
+    pub honba: HonbaCounter,
+    /// Number of riichi sticks (kyoutaku) on the table.
+    pub riichi_sticks: RiichiStickCounter,
+    /// Players who declared riichi this hand.
+    pub riichi: Vec<usize>,
+    /// Number of hands in a row the dealer has kept their seat (renchan).
+    pub renchan: u32,
+    /// Why the game ended, `None` while it is being played.
+    pub end: Option<GameEnd>,
+}
+
+impl Game {
+    /// Start a new game, with three players when the ruleset is sanma.
+    pub fn new(game_rules: GameRules, rules: Ruleset) -> Self {
+        let players = if rules.sanma.is_some() { 3 } else { 4 };
+        Self {
+            points: vec![game_rules.starting_points; players],
+            game_rules,
+            rules,
+            round: 0,
+            honba: 0,
+            riichi_sticks: 0,
+            riichi: vec![],
+            renchan: 0,
+            end: None,
+        }
+    }
+
+    /// Get the number of players.
+    pub fn players(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Get the player who is dealer.
+    pub fn dealer(&self) -> usize {
+        self.round % self.players()
+    }
+
+    /// Get the prevalent (round) wind.
+    pub fn round_wind(&self) -> TileGroup {
+        wind(self.round / self.players())
+    }
+
+    /// Get the hand number within the round wind, starting from 1.
+    pub fn round_number(&self) -> usize {
+        self.round % self.players() + 1
+    }
+
+    /// Check if the game is in its last round (all-last).
+    pub fn is_all_last(&self) -> bool {
+        self.round + 1 >= self.last_round()
+    }
+
+    /// Get the seat wind of a player this hand.
+    pub fn seat(&self, player: usize) -> TileGroup {
+        wind((player + self.players() - self.dealer()) % self.players())
+    }
+
+    /// Get the player sitting in a seat this hand.
+    pub fn player(&self, seat: &TileGroup) -> Result<usize, HandErr> {
+        let seat = seat_index(seat)?;
+        if seat >= self.players() {
+            return Err(HandErr::InvalidSeat);
+        }
+        Ok((seat + self.dealer()) % self.players())
+    }
+
+    /// Put down a riichi stick for the player in a seat, once a hand and only with the points for it.
+    pub fn declare_riichi(&mut self, seat: &TileGroup) -> Result<(), HandErr> {
+        if self.end.is_some() {
+            return Err(HandErr::GameOver);
+        }
+        let player = self.player(seat)?;
+        if self.riichi.contains(&player) {
+            return Err(HandErr::RiichiAlreadyDeclared);
+        }
+        if self.points[player] < RIICHI_DEPOSIT as PointDelta {
+            return Err(HandErr::RiichiWithoutPoints);
+        }
+        self.points[player] -= RIICHI_DEPOSIT as PointDelta;
+        self.riichi_sticks += 1;
+        self.riichi.push(player);
+        Ok(())
+    }
+
+    /// Get the context a win in a seat is scored with, with the honba, riichi sticks and riichi declarations of the game.
+    ///
+    /// A context with riichi from a player who didn't declare it is rejected.
+    pub fn win_context(
+        &self,
+        seat: &TileGroup,
+        context: &WinContext,
+    ) -> Result<WinContext, HandErr> {
+        self.context(seat, context)?.build()
+    }
+
+    /// Score the outcome of a hand and move the game on, returning each player's point change.
+    ///
+    /// Riichi sticks left on the table when the game ends go to the player in first place.
+    pub fn record(&mut self, outcome: HandOutcome) -> Result<Vec<PointDelta>, HandErr> {
+        if self.end.is_some() {
+            return Err(HandErr::GameOver);
+        }
+
+        let (seat_deltas, won, dealer_stays, dealer_tenpai) = match outcome {
+            HandOutcome::Tsumo(hand, context) => {
+                self.check_round(&hand)?;
+                let seat = hand.seat_tile();
+                let context = self.context(&seat, &context)?.tsumo(true).build()?;
+                let score = get_hand_score(hand, &context, &self.rules)?;
+                let settlement = settle(&score, &seat, None, &self.rules)?;
+                (settlement.deltas, true, seat_index(&seat)? == 0, false)
+            }
+            HandOutcome::Ron { wins, discarder } => {
+                let mut game_wins = vec![];
+                for (hand, context) in wins {
+                    self.check_round(&hand)?;
+                    let context = self.context(&hand.seat_tile(), &context)?.build()?;
+                    game_wins.push((hand, context));
+                }
+                match multiple_ron(game_wins, &discarder, &self.rules)? {
+                    MultipleRon::Wins {
+                        winners,
+                        settlement,
+                    } => {
+                        let dealer_won = winners.iter().any(|(seat, _)| seat_index(seat) == Ok(0));
+                        (settlement.deltas, true, dealer_won, false)
+                    }
+                    MultipleRon::AbortiveDraw => (vec![], false, true, false),
+                }
+            }
+            HandOutcome::ExhaustiveDraw { tenpai } => {
+                let draw = exhaustive_draw(&tenpai, self.honba, &self.rules)?;
+                (draw.deltas, false, draw.dealer_stays, draw.dealer_stays)
+            }
+            HandOutcome::AbortiveDraw => (vec![], false, true, false),
+            HandOutcome::NagashiMangan {
+                seat,
+                discards,
+                called,
+                tenpai,
+            } => {
+                let context = self
+                    .context(&seat, &WinContext::default())?
+                    .tsumo(true)
+                    .build()?;
+                let score = nagashi_mangan(&discards, called, &context, &self.rules)?;
+                let settlement = settle(&score, &seat, None, &self.rules)?;
+                if self.rules.nagashi_mangan == Some(NagashiMangan::Win) {
+                    (settlement.deltas, true, seat_index(&seat)? == 0, false)
+                } else {
+                    // nothing else is paid, the dealer keeps the seat when tenpai
+                    let draw = exhaustive_draw(&tenpai, self.honba, &self.rules)?;
+                    (
+                        settlement.deltas,
+                        false,
+                        draw.dealer_stays,
+                        draw.dealer_stays,
+                    )
+                }
+            }
+        };
+
+        let mut deltas = vec![0; self.players()];
+        for (seat, delta) in seat_deltas.into_iter().enumerate() {
+            add_delta(&mut deltas[(seat + self.dealer()) % self.players()], delta)?;
+        }
+        for (points, delta) in self.points.iter_mut().zip(&deltas) {
+            add_delta(points, *delta)?;
+        }
+        if won {
+            self.riichi_sticks = 0;
+        }
+        self.riichi.clear();
+
+        let all_last = self.is_all_last();
+        let dealer_top = self.top_player() == self.dealer();
+        if dealer_stays {
+            self.honba += 1;
+            self.renchan += 1;
+        } else {
+            self.honba = if won { 0 } else { self.honba + 1 };
+            self.renchan = 0;
+            self.round += 1;
+        }
+
+        self.end = if self.game_rules.tobi && self.points.iter().any(|points| *points < 0) {
+            Some(GameEnd::Tobi)
+        } else if all_last && won && dealer_stays && dealer_top && self.game_rules.agari_yame {
+            Some(GameEnd::AgariYame)
+        } else if all_last && dealer_tenpai && dealer_top && self.game_rules.tenpai_yame {
+            Some(GameEnd::TenpaiYame)
+        } else if self.round >= self.last_round() {
+            Some(GameEnd::LastRound)
+        } else {
+            None
+        };
+        if self.end.is_some() {
+            let top = self.top_player();
+            let riichi_sticks = self
+                .riichi_sticks
+                .checked_mul(RIICHI_DEPOSIT)
+                .ok_or(HandErr::ScoreOverflow)?;
+            let riichi_sticks = to_delta(riichi_sticks)?;
+            add_delta(&mut self.points[top], riichi_sticks)?;
+            add_delta(&mut deltas[top], riichi_sticks)?;
+            self.riichi_sticks = 0;
+        }
+
+        Ok(deltas)
+    }
+
+    /// Get the player in first place, the first in seat order of the first hand on a tie.
+    pub fn top_player(&self) -> usize {
+        let mut top = 0;
+        for (player, points) in self.points.iter().enumerate() {
+            if *points > self.points[top] {
+                top = player;
+            }
+        }
+        top
+    }
+
+    fn last_round(&self) -> usize {
+        self.game_rules.length.winds() * self.players()
+    }
+
+    fn check_round(&self, hand: &Hand) -> Result<(), HandErr> {
+        if hand.prev_tile() != self.round_wind() {
+            return Err(HandErr::WrongRoundWind);
+        }
+        self.player(&hand.seat_tile())?;
+        Ok(())
+    }
+
+    fn context(
+        &self,
+        seat: &TileGroup,
+        context: &WinContext,
+    ) -> Result<WinContextBuilder, HandErr> {
+        let declared = self.riichi.contains(&self.player(seat)?);
+        if !declared && (context.riichi() || context.doubleriichi()) {
+            return Err(HandErr::RiichiNotDeclared);
+        }
+        Ok(context
+            .to_builder()
+            .riichi(context.riichi() || (declared && !context.doubleriichi()))
+            .honba(self.honba)
+            .riichi_sticks(self.riichi_sticks))
+    }
+}
+
+fn wind(index: usize) -> TileGroup {
+    SEATS[index % SEATS.len()]
+        .to_string()
+        .try_into()
+        .expect("seat winds are valid tiles")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand::test_hand;
+
+    fn seat(seat: &str) -> TileGroup {
+        seat.to_string().try_into().unwrap()
+    }
+
+    #[test]
+    fn dealer_rotation() {
+        let mut game = Game::new(GameRules::default(), Ruleset::default());
+        // dealer keeps the seat with a tsumo
+        let east = test_hand(&["123m", "456p", "789s", "EEEw", "11s"], "1s", "Ew", "Ew");
+        let deltas = game
+            .record(HandOutcome::Tsumo(east, WinContext::default()))
+            .unwrap();
+        assert_eq!(deltas, [7_800, -2_600, -2_600, -2_600]);
+        assert_eq!((game.dealer(), game.honba, game.renchan), (0, 1, 1));
+
+        // a draw with the dealer noten passes the seat and keeps the honba
+        game.record(HandOutcome::ExhaustiveDraw {
+            tenpai: vec![seat("Sw")],
+        })
+        .unwrap();
+        assert_eq!((game.dealer(), game.honba, game.renchan), (1, 2, 0));
+        assert_eq!(game.seat(0), seat("Nw"));
+        assert_eq!(game.player(&seat("Ew")), Ok(1));
+
+        game.record(HandOutcome::AbortiveDraw).unwrap();
+        assert_eq!((game.dealer(), game.honba), (1, 3));
+
+        // the old dealer is now north, the honba go with the win
+        let north = test_hand(&["123m", "456p", "789s", "EEEw", "11s"], "1s", "Ew", "Nw");
+        let ron = HandOutcome::Ron {
+            wins: vec![(north, WinContext::default())],
+            discarder: seat("Ew"),
+        };
+        let deltas = game.record(ron).unwrap();
+        assert_eq!(deltas, [1_300 + 900, -1_300 - 900, 0, 0]);
+        assert_eq!((game.dealer(), game.honba), (2, 0));
+        assert_eq!(game.round_wind(), seat("Ew"));
+        assert_eq!(game.round_number(), 3);
+
+        // paid like a mangan tsumo, the draw keeps the honba and passes the seat of the noten dealer
+        let nagashi = HandOutcome::NagashiMangan {
+            seat: seat("Ww"),
+            discards: crate::decompose::parse_tiles("19m19p1234z").unwrap(),
+            called: false,
+            tenpai: vec![],
+        };
+        let deltas = game.record(nagashi).unwrap();
+        assert_eq!(deltas, [8_000, -2_000, -4_000, -2_000]);
+        assert_eq!((game.dealer(), game.honba), (3, 1));
+
+        let wrong_wind = test_hand(&["123m", "456p", "789s", "EEEw", "11s"], "1s", "Sw", "Ew");
+        assert_eq!(
+            game.record(HandOutcome::Tsumo(wrong_wind, WinContext::default())),
+            Err(HandErr::WrongRoundWind)
+        );
+    }
+
+    #[test]
+    fn riichi_declarations() {
+        let mut game = Game::new(GameRules::default(), Ruleset::default());
+        game.declare_riichi(&seat("Sw")).unwrap();
+        assert_eq!(
+            game.declare_riichi(&seat("Sw")),
+            Err(HandErr::RiichiAlreadyDeclared)
+        );
+        game.points[3] = 900;
+        assert_eq!(
+            game.declare_riichi(&seat("Nw")),
+            Err(HandErr::RiichiWithoutPoints)
+        );
+        assert_eq!((game.riichi.clone(), game.riichi_sticks), (vec![1], 1));
+
+        // only the player who declared can win with riichi
+        let riichi = WinContext::default()
+            .to_builder()
+            .riichi(true)
+            .build()
+            .unwrap();
+        let west = test_hand(&["123m", "456p", "789s", "234m", "55p"], "5p", "Ew", "Ww");
+        assert_eq!(
+            game.record(HandOutcome::Tsumo(west, riichi)),
+            Err(HandErr::RiichiNotDeclared)
+        );
+        assert!(game
+            .win_context(&seat("Sw"), &WinContext::default())
+            .unwrap()
+            .riichi());
+
+        // the declared player wins with riichi even when the context leaves it out
+        let south = test_hand(&["123m", "456p", "789s", "234m", "55p"], "5p", "Ew", "Sw");
+        let ron = HandOutcome::Ron {
+            wins: vec![(south, WinContext::default())],
+            discarder: seat("Ww"),
+        };
+        let deltas = game.record(ron).unwrap();
+        assert_eq!(deltas, [0, 1_300 + 1_000, -1_300, 0]);
+        assert!(game.riichi.is_empty());
+        game.declare_riichi(&seat("Sw")).unwrap();
+    }
+
+    #[test]
+    fn game_end() {
+        let mut game = Game::new(
+            GameRules {
+                length: GameLength::Tonpuusen,
+                agari_yame: true,
+                ..GameRules::default()
+            },
+            Ruleset::default(),
+        );
+        for _ in 0..3 {
+            game.record(HandOutcome::ExhaustiveDraw { tenpai: vec![] })
+                .unwrap();
+        }
+        assert!(game.is_all_last());
+        game.declare_riichi(&seat("Sw")).unwrap();
+
+        // the dealer of east 4 is player 3, who wins in first place
+        let dealer = test_hand(&["123m", "456p", "789s", "EEEw", "11s"], "1s", "Ew", "Ew");
+        game.record(HandOutcome::Tsumo(dealer, WinContext::default()))
+            .unwrap();
+        assert_eq!(game.end, Some(GameEnd::AgariYame));
+        assert_eq!(
+            game.points,
+            [
+                24_000 - 2_900,
+                25_000 - 2_900,
+                25_000 - 2_900,
+                25_000 + 8_700 + 1_000
+            ]
+        );
+        assert_eq!(
+            game.record(HandOutcome::AbortiveDraw),
+            Err(HandErr::GameOver)
+        );
+
+        let mut game = Game::new(GameRules::default(), Ruleset::default());
+        game.points = vec![1_000, 25_000, 25_000, 49_000];
+        let tsumo = test_hand(&["123m", "456p", "789s", "SSSw", "11s"], "1s", "Ew", "Sw");
+        game.record(HandOutcome::Tsumo(tsumo, WinContext::default()))
+            .unwrap();
+        assert_eq!(game.end, Some(GameEnd::Tobi));
+        assert_eq!(
+            game.points,
+            [-300, 25_000 + 2_700, 25_000 - 700, 49_000 - 700]
+        );
+
+        let mut game = Game::new(
+            GameRules {
+                length: GameLength::Tonpuusen,
+                ..GameRules::default()
+            },
+            Ruleset::default(),
+        );
+        for _ in 0..3 {
+            game.record(HandOutcome::AbortiveDraw).unwrap();
+        }
+        assert!(!game.is_all_last());
+        for _ in 0..3 {
+            game.record(HandOutcome::ExhaustiveDraw { tenpai: vec![] })
+                .unwrap();
+        }
+        game.declare_riichi(&seat("Sw")).unwrap();
+        game.record(HandOutcome::ExhaustiveDraw {
+            tenpai: vec![seat("Sw")],
+        })
+        .unwrap();
+        assert_eq!(game.end, Some(GameEnd::LastRound));
+        assert_eq!(game.honba, 7);
+        // the tenpai player takes the noten payments and the riichi stick left on the table
+        assert_eq!(
+            game.points,
+            [24_000 + 3_000 + 1_000, 24_000, 24_000, 24_000]
+        );
+    }
+}
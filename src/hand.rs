@@ -1,11 +1,14 @@
 pub mod error;
+pub mod wait;
 
 use crate::fu::Fu;
+use crate::score::Dora;
 use crate::suit::Suit;
-use crate::tile_group::{GroupType, TileGroup};
+use crate::tile_group::{tile_counts, GroupType, TileGroup};
 use error::HandErr;
+use wait::{Wait, WaitType};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Hand {
     groups: Vec<TileGroup>,
     win_tile: TileGroup,
@@ -13,6 +16,8 @@ pub struct Hand {
     /// Prevalent or round wind.
     prev_tile: TileGroup,
     isopen: bool,
+    /// The group completed by the winning tile and the shape of the wait.
+    wait: Wait,
 }
 
 impl Hand {
@@ -45,41 +50,30 @@ impl Hand {
         {
             return Err(HandErr::InvalidShape);
         }
-        // check if last group contains the winning tile
-        // FUCK handling kokuushi
-        let tilecount: u8 = groups.iter().map(|s| s.group_type.tile_count()).sum();
-        if tilecount == 13 {
-            let last_group = groups.last().unwrap();
-            match last_group.group_type {
-                GroupType::Sequence => {
-                    if win_tile.suit != last_group.suit {
-                        return Err(HandErr::InvalidShape);
-                    }
-
-                    let win_int = win_tile.parse_u8().unwrap();
-                    let last_int = last_group.parse_u8().unwrap();
 
-                    if win_int != last_int && win_int != last_int + 1 && win_int != last_int + 2 {
-                        return Err(HandErr::InvalidShape);
-                    }
-                }
-                GroupType::Triplet | GroupType::Pair => {
-                    if last_group.value != win_tile.value || last_group.suit != win_tile.suit {
-                        return Err(HandErr::InvalidShape);
-                    }
-                }
-                GroupType::Kan | GroupType::None => return Err(HandErr::InvalidShape),
-            }
-        }
-
-        Ok(Hand {
+        let mut hand = Hand {
             groups,
             win_tile,
             seat_tile,
             prev_tile,
             isopen,
-        })
+            wait: Wait {
+                group: 0,
+                wait_type: WaitType::Tanki,
+            },
+        };
+
+        // the last group is preferred when the winning tile completes more than one, scoring tries every wait anyway
+        let waits = hand.waits();
+        hand.wait = *waits
+            .iter()
+            .find(|wait| wait.group == hand.groups.len() - 1)
+            .or(waits.first())
+            .ok_or(HandErr::InvalidShape)?;
+
+        Ok(hand)
     }
+
     pub fn new_from_strings(
         tiles: Vec<String>,
         win: String,
@@ -113,15 +107,18 @@ impl Hand {
             fu_types.push(Fu::Tsumo);
         }
 
-        if !self.is_open() {
+        if !tsumo && !self.is_open() {
             fu_types.push(Fu::ClosedRon);
         }
 
         //meld fu cal
-        for tile_group in &self.triplets() {
+        for (i, tile_group) in self.groups.iter().enumerate() {
+            if tile_group.group_type != GroupType::Triplet {
+                continue;
+            }
             let group_is_terminal_or_honor = tile_group.is_honor() || tile_group.isterminal;
 
-            if tile_group == self.groups.last().unwrap() {
+            if i == self.wait.group {
                 if tsumo {
                     if group_is_terminal_or_honor {
                         fu_types.push(Fu::NonSimpleClosedTriplet);
@@ -177,27 +174,14 @@ impl Hand {
         }
 
         //fu wait cal
-        if let Some(group) = self.groups.last() {
-            match group.group_type {
-                GroupType::Pair => fu_types.push(Fu::SingleWait),
-                GroupType::Sequence => {
-                    let mid_tile = group.parse_u8().unwrap() + 1;
-                    if self.win_tile().parse_u8().unwrap() == mid_tile {
-                        fu_types.push(Fu::SingleWait);
-                    }
-
-                    if !self.win_tile().isterminal && group.isterminal {
-                        fu_types.push(Fu::SingleWait);
-                    }
-                }
-                _ => {}
-            }
+        if self.wait.wait_type.is_single_wait() {
+            fu_types.push(Fu::SingleWait);
         }
 
         fu_types
     }
 
-    /// Get the dora count in the hand from dora indicator tiles.
+    /// Get the dora in the hand from the dora and ura dora indicator tiles, with red fives counted apart.
     ///
     /// # Examples
     ///
@@ -221,25 +205,47 @@ impl Hand {
     ///    "1p".to_string().try_into().unwrap(),
     ///    "4s".to_string().try_into().unwrap(),
     ///    "Nw".to_string().try_into().unwrap(),
+    /// ];
+    /// let uras: Vec<TileGroup> = vec![
     ///    "8m".to_string().try_into().unwrap(),
     ///    "gd".to_string().try_into().unwrap(),
     /// ];
     ///
-    /// let dora = hand.get_dora_count(Some(doras));
-    /// assert_eq!(dora, 14);
+    /// let dora = hand.get_dora_count(&doras, &uras);
+    /// assert_eq!((dora.dora, dora.aka, dora.ura), (7, 1, 6));
+    /// assert_eq!(dora.total(), 14);
     /// ```
-    pub fn get_dora_count(&self, dora_indicator_tiles: Option<Vec<TileGroup>>) -> u32 {
-        let mut count = 0;
-        for group in &self.groups {
-            if group.isaka {
-                count += 1;
-            }
-        }
-        if dora_indicator_tiles.is_none() {
-            return count;
+    pub fn get_dora_count(
+        &self,
+        dora_indicators: &[TileGroup],
+        ura_indicators: &[TileGroup],
+    ) -> Dora {
+        Dora {
+            dora: self.indicator_dora_count(dora_indicators),
+            aka: self.aka_count(),
+            ura: self.indicator_dora_count(ura_indicators),
+            nukidora: 0,
         }
-        for tile in dora_indicator_tiles.unwrap() {
-            let dora_tile = tile.next_tile().unwrap();
+    }
+
+    /// Get the number of red fives in the hand.
+    pub fn aka_count(&self) -> u32 {
+        self.groups.iter().filter(|group| group.isaka).count() as u32
+    }
+
+    /// Get the number of dora in the hand from dora (or ura dora) indicator tiles, without red fives.
+    pub fn indicator_dora_count(&self, dora_indicator_tiles: &[TileGroup]) -> u32 {
+        let dora_tiles: Vec<TileGroup> = dora_indicator_tiles
+            .iter()
+            .map(|tile| tile.next_tile().unwrap())
+            .collect();
+        self.dora_tile_count(&dora_tiles)
+    }
+
+    /// Get the number of dora in the hand from the dora tiles themselves, without red fives.
+    pub fn dora_tile_count(&self, dora_tiles: &[TileGroup]) -> u32 {
+        let mut count = 0;
+        for dora_tile in dora_tiles {
             for triplet in self.triplets() {
                 if triplet.value == dora_tile.value && triplet.suit == dora_tile.suit {
                     count += 3;
@@ -320,6 +326,72 @@ impl Hand {
             .collect()
     }
 
+    /// Get every group in the hand.
+    pub fn groups(&self) -> &[TileGroup] {
+        &self.groups
+    }
+
+    /// Get the group completed by the winning tile and the shape of the wait.
+    pub fn wait(&self) -> Wait {
+        self.wait
+    }
+
+    /// Get every wait the winning tile could have completed in this grouping.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::hand::Hand;
+    /// use mahc::hand::wait::WaitType;
+    ///
+    /// let hand = Hand::new_from_strings(
+    ///     vec![
+    ///         "123m".to_string(),
+    ///         "345m".to_string(),
+    ///         "456p".to_string(),
+    ///         "789s".to_string(),
+    ///         "11s".to_string(),
+    ///     ],
+    ///     "3m".to_string(),
+    ///     "Ew".to_string(),
+    ///     "Ew".to_string(),
+    /// )
+    /// .unwrap();
+    /// let waits: Vec<WaitType> = hand.waits().iter().map(|wait| wait.wait_type).collect();
+    /// assert_eq!(waits, [WaitType::Penchan, WaitType::Ryanmen]);
+    /// ```
+    pub fn waits(&self) -> Vec<Wait> {
+        let mut waits: Vec<Wait> = vec![];
+        for (i, group) in self.groups.iter().enumerate() {
+            let Some(mut wait_type) = WaitType::from_group(group, &self.win_tile) else {
+                continue;
+            };
+            // identical groups give the same wait
+            if waits.iter().any(|wait| &self.groups[wait.group] == group) {
+                continue;
+            }
+
+            if self.is_chuurenpoutou() && self.is_nine_sided() {
+                wait_type = WaitType::Chuuren9Sided;
+            } else if group.group_type == GroupType::Pair && self.is_kokushi() {
+                wait_type = WaitType::Kokushi13Sided;
+            }
+            waits.push(Wait {
+                group: i,
+                wait_type,
+            });
+        }
+        waits
+    }
+
+    /// Get the same grouping completed with a different wait, usually one from [`Hand::waits`].
+    pub fn with_wait(&self, wait: Wait) -> Self {
+        Self {
+            wait,
+            ..self.clone()
+        }
+    }
+
     /// Get the winning tile the completes the hand.
     pub fn win_tile(&self) -> TileGroup {
         self.win_tile.clone()
@@ -436,7 +508,7 @@ impl Hand {
             }
         }
 
-        if !tsumo && self.groups.last().unwrap().group_type == GroupType::Triplet {
+        if !tsumo && self.wait.wait_type == WaitType::Shanpon {
             closed_triplet_count -= 1;
         }
 
@@ -445,25 +517,7 @@ impl Hand {
 
     /// Check if the hand contains a mixed triple sequence (ex: `123m 123p 123s`).
     pub fn is_sanshokudoujun(&self) -> bool {
-        if self.sequences().len() < 3 {
-            return false;
-        }
-
-        let mut list_of_vals: Vec<String> = vec![];
-        for sequence_group in self.sequences() {
-            list_of_vals.push(sequence_group.value.clone());
-        }
-        list_of_vals.dedup();
-
-        if self.sequences().len() == 3 {
-            if list_of_vals.len() == 1 {
-                return true;
-            }
-        } else if list_of_vals.len() == 2 {
-            return true;
-        }
-
-        false
+        Self::is_sanshoku(&self.sequences())
     }
 
     /// Check if the hand only contains tiles of one suit and any honor tiles.
@@ -627,7 +681,7 @@ impl Hand {
             return false;
         }
 
-        if self.isopen {
+        if self.isopen || self.wait.wait_type != WaitType::Ryanmen {
             return false;
         }
 
@@ -638,25 +692,19 @@ impl Hand {
 
     /// Check if the hand contains three triplets (or quads) of the same value across the three numerical suits (manzu, pinzu, and souzu).
     pub fn is_sanshokudoukou(&self) -> bool {
-        if self.triplets().len() + self.kans().len() < 3 {
-            return false;
-        }
-
-        let mut list_of_vals: Vec<String> = vec![];
-        for group in self.triplets().iter().chain(self.kans().iter()) {
-            list_of_vals.push(group.value.clone());
-        }
-        list_of_vals.dedup();
-
-        if self.triplets().len() + self.kans().len() == 3 {
-            if list_of_vals.len() == 1 {
-                return true;
-            }
-        } else if list_of_vals.len() == 2 {
-            return true;
-        }
-
-        false
+        let groups: Vec<TileGroup> = self.triplets().into_iter().chain(self.kans()).collect();
+        Self::is_sanshoku(&groups)
+    }
+
+    /// Check if a group of the same value appears in each of the three numerical suits.
+    fn is_sanshoku(groups: &[TileGroup]) -> bool {
+        groups.iter().any(|group| {
+            [Suit::Manzu, Suit::Pinzu, Suit::Souzu].iter().all(|suit| {
+                groups
+                    .iter()
+                    .any(|other| &other.suit == suit && other.value == group.value)
+            })
+        })
     }
 
     /// Check if the hand only contains tiles of a single suit.
@@ -699,7 +747,7 @@ impl Hand {
             return false;
         }
 
-        if !tsumo && self.groups.last().unwrap().group_type == GroupType::Triplet {
+        if !tsumo && self.wait.wait_type == WaitType::Shanpon {
             return false;
         }
 
@@ -718,11 +766,7 @@ impl Hand {
             return false;
         }
 
-        if self.groups.last().unwrap().group_type == GroupType::Pair {
-            return true;
-        }
-
-        false
+        self.wait.wait_type == WaitType::Tanki
     }
 
     /// Check if the hand only contains terminal tiles.
@@ -779,65 +823,48 @@ impl Hand {
 
     /// Check if the hand consists of 1112345678999 in the same suit, plus one additional tile of that suit.
     pub fn is_chuurenpoutou(&self) -> bool {
-        if self.groups.len() == 13 {
+        let Some(counts) = self.suit_counts() else {
             return false;
-        }
-
-        let suit: Suit = self.groups[0].suit.clone();
-        if self.triplets().len() != 2 || self.sequences().len() != 2 || self.pairs().len() != 1 {
-            return false;
-        }
-
-        for group in self.groups.clone() {
-            if group.suit != suit {
-                return false;
-            }
-        }
-
-        let has_1 = self.triplets().clone().iter().any(|i| i.value == "1");
-        let has_9 = self.triplets().clone().iter().any(|i| i.value == "9");
-        if !has_1 || !has_9 {
+        };
+        if self.isopen || !self.kans().is_empty() {
             return false;
         }
 
-        let mut vals: Vec<u8> = vec![];
-        for sequence_group in self.sequences() {
-            let int = sequence_group.value.parse::<u8>().unwrap();
-            vals.push(int);
-            vals.push(int + 1);
-            vals.push(int + 2);
-        }
-
-        for pair_group in self.pairs() {
-            let int = pair_group.value.parse::<u8>().unwrap();
-            vals.push(int);
-        }
-
-        vals.sort();
-        if vals != [2, 3, 4, 5, 6, 7, 8] {
-            return false;
-        }
-
-        true
+        counts[0] >= 3 && counts[8] >= 3 && counts.iter().all(|&count| count >= 1)
     }
 
     /// Check if the hand consists of 1112345678999 in the same suit, plus one additional tile of that suit.
     ///
     /// This variant checks that the hand was completed with a 9-sided wait.
     pub fn is_chuurenpoutou9sided(&self) -> bool {
-        if self.groups.len() == 13 {
-            return false;
-        }
+        self.is_chuurenpoutou() && self.wait.wait_type == WaitType::Chuuren9Sided
+    }
 
-        if !self.is_chuurenpoutou() {
+    /// Check if the tiles before the winning tile were exactly 1112345678999 of one suit.
+    fn is_nine_sided(&self) -> bool {
+        let Some(mut counts) = self.suit_counts() else {
             return false;
-        }
+        };
+        counts[self.win_tile.tile_index() % 9] -= 1;
 
-        if self.groups.last().unwrap().group_type != GroupType::Pair {
-            return false;
+        counts == [3, 1, 1, 1, 1, 1, 1, 1, 3]
+    }
+
+    /// Count the tiles of each value when the hand only holds tiles of a single numbered suit.
+    fn suit_counts(&self) -> Option<[u8; 9]> {
+        let suit = &self.groups[0].suit;
+        if self.groups.len() == 13
+            || self
+                .groups
+                .iter()
+                .any(|group| group.is_honor() || &group.suit != suit)
+        {
+            return None;
         }
 
-        true
+        let base = self.groups[0].tile_index() / 9 * 9;
+        let counts = tile_counts(&self.groups);
+        Some(counts[base..base + 9].try_into().unwrap())
     }
 
     /// Check if the hand only consists of honor tiles.
@@ -922,7 +949,7 @@ impl Hand {
 
     /// Check if the hand has one of each type of terminal and honor tile and one additional terminal or honor tile, on a 13-sided wait.
     pub fn is_kokushi13sided(&self) -> bool {
-        self.is_kokushi() && self.groups.last().unwrap().group_type == GroupType::Pair
+        self.is_kokushi() && self.wait.wait_type == WaitType::Kokushi13Sided
     }
 
     /// Check if the player is the dealer and has a winning hand in the uninterrupted first turn.
@@ -946,6 +973,18 @@ impl Hand {
     }
 }
 
+/// Build a hand from group strings for tests, panicking when it is invalid.
+#[cfg(test)]
+pub(crate) fn test_hand(groups: &[&str], win: &str, prev: &str, seat: &str) -> Hand {
+    Hand::new_from_strings(
+        groups.iter().map(|group| group.to_string()).collect(),
+        win.to_string(),
+        prev.to_string(),
+        seat.to_string(),
+    )
+    .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::Hand;
@@ -1301,6 +1340,23 @@ mod tests {
         )
         .unwrap();
         assert!(!out.is_chuurenpoutou9sided());
+
+        // the extra 1 can be grouped without the two outer triplets
+        let out = Hand::new_from_strings(
+            vec![
+                "111s".to_string(),
+                "123s".to_string(),
+                "456s".to_string(),
+                "789s".to_string(),
+                "99s".to_string(),
+            ],
+            "1s".to_string(),
+            "Ew".to_string(),
+            "Ww".to_string(),
+        )
+        .unwrap();
+        assert!(out.is_chuurenpoutou());
+        assert!(out.is_chuurenpoutou9sided());
     }
 
     #[test]
@@ -1612,6 +1668,21 @@ mod tests {
         )
         .unwrap();
         assert!(!out.is_sanshokudoukou());
+
+        let out = Hand::new_from_strings(
+            vec![
+                "111m".to_string(),
+                "111p".to_string(),
+                "999m".to_string(),
+                "999p".to_string(),
+                "11s".to_string(),
+            ],
+            "1s".to_string(),
+            "Ew".to_string(),
+            "Ww".to_string(),
+        )
+        .unwrap();
+        assert!(!out.is_sanshokudoukou());
     }
 
     #[test]
@@ -2172,6 +2243,21 @@ mod tests {
         )
         .unwrap();
         assert!(!out.is_sanshokudoujun());
+
+        let out = Hand::new_from_strings(
+            vec![
+                "234m".to_string(),
+                "234m".to_string(),
+                "567p".to_string(),
+                "567p".to_string(),
+                "11s".to_string(),
+            ],
+            "1s".to_string(),
+            "Ew".to_string(),
+            "Ww".to_string(),
+        )
+        .unwrap();
+        assert!(!out.is_sanshokudoujun());
     }
 
     #[test]
@@ -2212,7 +2298,7 @@ mod tests {
                 "2222m".to_string(),
                 "EEEw".to_string(),
                 "11s".to_string(),
-                "456so".to_string(),
+                "456s".to_string(),
             ],
             "5s".to_string(),
             "Ew".to_string(),
@@ -2695,8 +2781,9 @@ mod tile_group_tests {
         let dora_5: TileGroup = "gd".to_string().try_into().unwrap();
         let doras = vec![dora_1, dora_2, dora_3, dora_4, dora_5];
 
-        let dora = out.get_dora_count(Some(doras));
-        assert_eq!(dora, 14);
+        let dora = out.get_dora_count(&doras, &[]);
+        assert_eq!(dora.dora, 13);
+        assert_eq!(dora.total(), 14);
     }
     #[test]
     fn dora_count_aka() {
@@ -2713,7 +2800,8 @@ mod tile_group_tests {
             "Ew".to_string(),
         )
         .unwrap();
-        let dora = out.get_dora_count(None);
-        assert_eq!(dora, 1);
+        let dora = out.get_dora_count(&[], &[]);
+        assert_eq!(dora.aka, 1);
+        assert_eq!(dora.total(), 1);
     }
 }
@@ -9,6 +9,7 @@ pub enum HandErr {
     DuplicateRiichi,
     IppatsuWithoutRiichi,
     DoubleRiichiHaiteiIppatsu,
+    #[deprecated(note = "a last tile win with chankan is rejected as `HouteiChankan`")]
     DoubleRiichiHaiteiChankan,
     ChankanTsumo,
     RinshanKanWithoutKan,
@@ -16,6 +17,34 @@ pub enum HandErr {
     RinshanIppatsu,
     NoHan,
     NoFu,
+    NotTenpai,
+    FuritenRon,
+    RiichiOpenHand,
+    TenhouWithCalls,
+    HaiteiRinshan,
+    HouteiChankan,
+    UraDoraWithoutRiichi,
+    TooManyAkaDora,
+    ScoreOverflow,
+    InvalidSanmaTile,
+    NukidoraWithoutSanma,
+    InvalidSeat,
+    MultipleRonTsumo,
+    WrongRoundWind,
+    GameOver,
+    NoGame,
+    GameNotSaved,
+    RiichiAlreadyDeclared,
+    RiichiWithoutPoints,
+    RiichiNotDeclared,
+    NotNagashiMangan,
+    NoNagashiMangan,
+    InvalidUma,
+    InvalidPlayer,
+    InvalidPayment,
+    TooManyDoraIndicators,
+    TenhouWithoutTsumo,
+    TenhouRiichi,
 }
 
 impl std::fmt::Display for HandErr {
@@ -36,11 +65,47 @@ impl std::fmt::Display for HandErr {
             Self::DoubleRiichiHaiteiIppatsu => {
                 write!(f, "Cant Double Riichi, Ippatsu and Haitei")
             }
+            #[allow(deprecated)]
             Self::DoubleRiichiHaiteiChankan => {
-                write!(f, "Cant Double Riichi, Ippatsu and Haitei")
+                write!(f, "Cant Double Riichi, Haitei and Chankan")
             }
             Self::NoHan => write!(f, "No Han provided!"),
             Self::NoFu => write!(f, "No Fu provided!"),
+            Self::NotTenpai => write!(f, "Hand is not Tenpai"),
+            Self::FuritenRon => write!(f, "Cant Ron while Furiten"),
+            Self::RiichiOpenHand => write!(f, "Cant Riichi with an Open Hand"),
+            Self::TenhouWithCalls => write!(f, "Cant Tenhou/Chiihou with a Call or Kan"),
+            Self::HaiteiRinshan => write!(f, "Cant Haitei and Rinshan"),
+            Self::HouteiChankan => write!(f, "Cant Houtei and Chankan"),
+            Self::UraDoraWithoutRiichi => write!(f, "Cant have Ura Dora without Riichi"),
+            Self::TooManyAkaDora => write!(f, "More Aka Dora than the Ruleset has"),
+            Self::ScoreOverflow => write!(f, "Score is too large to count"),
+            Self::InvalidSanmaTile => write!(f, "2m to 8m are not used in Sanma"),
+            Self::NukidoraWithoutSanma => write!(f, "Cant have Nukidora outside of Sanma"),
+            Self::InvalidSeat => write!(f, "Invalid Seat found"),
+            Self::MultipleRonTsumo => write!(f, "Cant Tsumo in a Multiple Ron"),
+            Self::WrongRoundWind => write!(f, "Prevalent Wind does not match the Round"),
+            Self::GameOver => write!(f, "Game is Over"),
+            Self::NoGame => write!(f, "No Game found, start one with `mahc game new`"),
+            Self::GameNotSaved => write!(f, "Unable to save the Game"),
+            Self::RiichiAlreadyDeclared => write!(f, "Riichi was already declared this hand"),
+            Self::RiichiWithoutPoints => write!(f, "Cant Riichi with less than 1000 points"),
+            Self::RiichiNotDeclared => write!(f, "Cant win with Riichi without declaring it"),
+            Self::NotNagashiMangan => {
+                write!(
+                    f,
+                    "Nagashi Mangan needs only Terminal and Honor Discards, none Called"
+                )
+            }
+            Self::NoNagashiMangan => write!(f, "Nagashi Mangan is not played in the Ruleset"),
+            Self::InvalidUma => write!(f, "Uma needs a Value for every Place"),
+            Self::InvalidPlayer => write!(f, "Invalid Player found"),
+            Self::InvalidPayment => write!(f, "Invalid Payment found"),
+            Self::TooManyDoraIndicators => {
+                write!(f, "More Dora Indicators than one and one for each Kan")
+            }
+            Self::TenhouWithoutTsumo => write!(f, "Cant Tenhou/Chiihou without Tsumo"),
+            Self::TenhouRiichi => write!(f, "Cant Tenhou/Chiihou and Riichi"),
         }
     }
 }
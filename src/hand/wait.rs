@@ -0,0 +1,121 @@
+use crate::tile_group::{GroupType, TileGroup};
+
+/// The shape the winning tile completed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaitType {
+    /// Two-sided sequence wait (ex: `23` waiting on 1 or 4).
+    Ryanmen,
+    /// Closed sequence wait (ex: `13` waiting on 2).
+    Kanchan,
+    /// Edge sequence wait (ex: `12` waiting on 3).
+    Penchan,
+    /// Two pairs waiting to become a triplet.
+    Shanpon,
+    /// Single tile waiting to become the pair.
+    Tanki,
+    /// Kokushi musou waiting on any of the thirteen orphans.
+    Kokushi13Sided,
+    /// Chuuren poutou waiting on any tile of the suit.
+    Chuuren9Sided,
+}
+
+impl WaitType {
+    /// Get the wait type of the winning tile completing the group, if it can.
+    ///
+    /// Called groups and kans can never be completed by the winning tile.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::hand::wait::WaitType;
+    /// use mahc::tile_group::TileGroup;
+    ///
+    /// let group: TileGroup = "789m".to_string().try_into().unwrap();
+    /// let win_tile: TileGroup = "7m".to_string().try_into().unwrap();
+    /// assert_eq!(WaitType::from_group(&group, &win_tile), Some(WaitType::Penchan));
+    ///
+    /// let win_tile: TileGroup = "9m".to_string().try_into().unwrap();
+    /// assert_eq!(WaitType::from_group(&group, &win_tile), Some(WaitType::Ryanmen));
+    /// ```
+    pub fn from_group(group: &TileGroup, win_tile: &TileGroup) -> Option<Self> {
+        if group.isopen || !group.tile_indices().contains(&win_tile.tile_index()) {
+            return None;
+        }
+
+        match group.group_type {
+            GroupType::Sequence => {
+                let low = group.parse_u8().unwrap();
+                let win = win_tile.parse_u8().unwrap();
+                if win == low + 1 {
+                    Some(Self::Kanchan)
+                } else if (win == low && low == 7) || (win == low + 2 && low == 1) {
+                    Some(Self::Penchan)
+                } else {
+                    Some(Self::Ryanmen)
+                }
+            }
+            GroupType::Triplet => Some(Self::Shanpon),
+            GroupType::Pair | GroupType::None => Some(Self::Tanki),
+            GroupType::Kan => None,
+        }
+    }
+
+    /// Check if the wait only accepts a single tile and is awarded fu.
+    pub fn is_single_wait(&self) -> bool {
+        matches!(self, Self::Kanchan | Self::Penchan | Self::Tanki)
+    }
+}
+
+impl std::fmt::Display for WaitType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ryanmen => write!(f, "Ryanmen"),
+            Self::Kanchan => write!(f, "Kanchan"),
+            Self::Penchan => write!(f, "Penchan"),
+            Self::Shanpon => write!(f, "Shanpon"),
+            Self::Tanki => write!(f, "Tanki"),
+            Self::Kokushi13Sided => write!(f, "Kokushi 13 sided"),
+            Self::Chuuren9Sided => write!(f, "Chuuren 9 sided"),
+        }
+    }
+}
+
+/// The group the winning tile completed and the shape of the wait.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wait {
+    /// Index of the completed group in the hand.
+    pub group: usize,
+    /// Shape of the wait.
+    pub wait_type: WaitType,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_type(group: &str, win: &str) -> Option<WaitType> {
+        let group: TileGroup = group.to_string().try_into().unwrap();
+        let win_tile: TileGroup = win.to_string().try_into().unwrap();
+        WaitType::from_group(&group, &win_tile)
+    }
+
+    #[test]
+    fn sequence_waits() {
+        assert_eq!(wait_type("123s", "1s"), Some(WaitType::Ryanmen));
+        assert_eq!(wait_type("123s", "2s"), Some(WaitType::Kanchan));
+        assert_eq!(wait_type("123s", "3s"), Some(WaitType::Penchan));
+        assert_eq!(wait_type("456p", "6p"), Some(WaitType::Ryanmen));
+        assert_eq!(wait_type("789m", "7m"), Some(WaitType::Penchan));
+        assert_eq!(wait_type("789m", "9m"), Some(WaitType::Ryanmen));
+        assert_eq!(wait_type("789m", "9p"), None);
+    }
+
+    #[test]
+    fn set_waits() {
+        assert_eq!(wait_type("EEEw", "Ew"), Some(WaitType::Shanpon));
+        assert_eq!(wait_type("55m", "0m"), Some(WaitType::Tanki));
+        assert_eq!(wait_type("9s", "9s"), Some(WaitType::Tanki));
+        assert_eq!(wait_type("EEEwo", "Ew"), None);
+        assert_eq!(wait_type("EEEEw", "Ew"), None);
+    }
+}
@@ -1,13 +1,24 @@
 pub mod calc;
+pub mod context;
+pub mod decompose;
 pub mod fu;
+pub mod furiten;
+pub mod game;
 pub mod hand;
 pub mod limit_hand;
+pub mod lookup;
+pub mod orasu;
+pub mod pao;
 pub mod payment;
+pub mod ruleset;
+pub mod ryuukyoku;
 pub mod score;
+pub mod settlement;
+pub mod shanten;
+pub mod standings;
 pub mod suit;
+pub mod table;
+pub mod tenpai;
 pub mod tile_group;
+pub mod ukeire;
 pub mod yaku;
-
-/// Characters that represent terminal or honor tiles.
-// Using a fixed array gets stored on the stack rather than a `String` which gets stored on the heap.
-const TERMINAL_CHARS: [char; 9] = ['1', '9', 'E', 'S', 'W', 'N', 'r', 'g', 'w'];
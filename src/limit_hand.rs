@@ -1,18 +1,41 @@
-use crate::payment::Payment;
+use crate::payment::{Payment, Points};
+use crate::ruleset::Ruleset;
 use crate::score::{FuValue, HanValue};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LimitHands {
     Mangan,
     Haneman,
     Baiman,
     Sanbaiman,
     KazoeYakuman,
+    /// One or more yakuman, counting double yakuman twice.
+    Yakuman(HanValue),
+}
+
+impl std::fmt::Display for LimitHands {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mangan => write!(f, "Mangan"),
+            Self::Haneman => write!(f, "Haneman"),
+            Self::Baiman => write!(f, "Baiman"),
+            Self::Sanbaiman => write!(f, "Sanbaiman"),
+            Self::KazoeYakuman => write!(f, "Kazoe Yakuman"),
+            Self::Yakuman(1) => write!(f, "Yakuman"),
+            Self::Yakuman(2) => write!(f, "Double Yakuman"),
+            Self::Yakuman(3) => write!(f, "Triple Yakuman"),
+            Self::Yakuman(count) => write!(f, "{count}x Yakuman"),
+        }
+    }
 }
 
 impl LimitHands {
-    /// Check if the score of the hand is limited (no aotenjou).
-    fn is_limit_hand(han: HanValue, fu: FuValue) -> bool {
+    /// Check if the score of the hand is limited, never with aotenjou.
+    fn is_limit_hand(han: HanValue, fu: FuValue, rules: &Ruleset) -> bool {
+        if rules.aotenjou {
+            return false;
+        }
+
         if han >= 5 {
             return true;
         }
@@ -25,26 +48,33 @@ impl LimitHands {
             return true;
         }
 
+        // kiriage mangan rounds the hands just short of mangan up, never chiitoitsu
+        let fu = if fu == 25 { fu } else { fu.div_ceil(10) * 10 };
+        if rules.kiriage_mangan && ((han == 4 && fu == 30) || (han == 3 && fu == 60)) {
+            return true;
+        }
+
         false
     }
 
     /// Calculate the limit hand type from the han and fu scores.
-    pub fn get_limit_hand(han: HanValue, fu: FuValue) -> Option<Self> {
-        if !Self::is_limit_hand(han, fu) {
+    ///
+    /// Without kazoe yakuman in the ruleset, 13 or more han is a sanbaiman.
+    pub fn get_limit_hand(han: HanValue, fu: FuValue, rules: &Ruleset) -> Option<Self> {
+        if !Self::is_limit_hand(han, fu, rules) {
             return None;
         }
 
-        // TODO: Allow (3 han, 70+ fu) and (4 han, 40+ fu) to be considered manga.
         if han <= 5 {
             Some(Self::Mangan)
         } else if han <= 7 {
-            return Some(Self::Haneman);
+            Some(Self::Haneman)
         } else if han <= 10 {
-            return Some(Self::Baiman);
-        } else if han <= 12 {
-            return Some(Self::Sanbaiman);
+            Some(Self::Baiman)
+        } else if han <= 12 || !rules.kazoe_yakuman {
+            Some(Self::Sanbaiman)
         } else {
-            return Some(Self::KazoeYakuman);
+            Some(Self::KazoeYakuman)
         }
     }
 
@@ -56,8 +86,53 @@ impl LimitHands {
             Self::Baiman => 4_000,
             Self::Sanbaiman => 6_000,
             Self::KazoeYakuman => 8_000,
+            Self::Yakuman(count) => 8_000 * Points::from(*count),
         };
 
         Payment::new(base_points)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kiriage_mangan() {
+        let rules = Ruleset::default();
+        assert!(LimitHands::get_limit_hand(4, 30, &rules).is_none());
+        assert!(LimitHands::get_limit_hand(3, 60, &rules).is_none());
+
+        let rules = Ruleset::wrc();
+        assert_eq!(
+            LimitHands::get_limit_hand(4, 30, &rules),
+            Some(LimitHands::Mangan)
+        );
+        assert_eq!(
+            LimitHands::get_limit_hand(3, 60, &rules),
+            Some(LimitHands::Mangan)
+        );
+        assert!(LimitHands::get_limit_hand(3, 50, &rules).is_none());
+        assert!(LimitHands::get_limit_hand(4, 25, &rules).is_none());
+    }
+
+    #[test]
+    fn kazoe_yakuman_cap() {
+        assert_eq!(
+            LimitHands::get_limit_hand(13, 30, &Ruleset::default()),
+            Some(LimitHands::KazoeYakuman)
+        );
+        assert_eq!(
+            LimitHands::get_limit_hand(13, 30, &Ruleset::ema()),
+            Some(LimitHands::Sanbaiman)
+        );
+    }
+
+    #[test]
+    fn yakuman_tiers() {
+        assert_eq!(LimitHands::Yakuman(1).to_string(), "Yakuman");
+        assert_eq!(LimitHands::Yakuman(2).to_string(), "Double Yakuman");
+        assert_eq!(LimitHands::Yakuman(3).to_string(), "Triple Yakuman");
+        assert_eq!(LimitHands::Yakuman(3).get_score().base_points(), 24_000);
+    }
+}
@@ -0,0 +1,300 @@
+use crate::calc::{calculate, is_possible_han_fu, rules_payment, FU_VALUES};
+use crate::hand::error::HandErr;
+use crate::limit_hand::LimitHands;
+use crate::payment::{Payment, Points};
+use crate::ruleset::Ruleset;
+use crate::score::{FuValue, HanValue, HonbaCounter};
+
+/// Han looked at without aotenjou, where 13 or more han is a kazoe yakuman or sanbaiman.
+const MAX_HAN: HanValue = 13;
+
+/// Most yakuman looked at in one hand.
+const MAX_YAKUMAN: HanValue = 6;
+
+/// A payment the way a scoreboard shows it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShownPayment {
+    /// Paid by the discarder (eg 7700).
+    Ron(Points),
+    /// Paid by every player to the dealer (eg 3900 all).
+    TsumoAll(Points),
+    /// Paid by the non-dealers and the dealer to a non-dealer (eg 2000/3900).
+    Tsumo { non_dealer: Points, dealer: Points },
+}
+
+impl TryFrom<String> for ShownPayment {
+    type Error = HandErr;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let value = value.trim().to_lowercase();
+        let points = |points: &str| {
+            points
+                .trim()
+                .parse::<Points>()
+                .map_err(|_| HandErr::InvalidPayment)
+        };
+
+        if let Some((non_dealer, dealer)) = value.split_once('/') {
+            Ok(Self::Tsumo {
+                non_dealer: points(non_dealer)?,
+                dealer: points(dealer)?,
+            })
+        } else if let Some(all) = value.strip_suffix("all") {
+            Ok(Self::TsumoAll(points(all)?))
+        } else {
+            Ok(Self::Ron(points(&value)?))
+        }
+    }
+}
+
+impl std::fmt::Display for ShownPayment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ron(points) => write!(f, "{points}"),
+            Self::TsumoAll(points) => write!(f, "{points} all"),
+            Self::Tsumo { non_dealer, dealer } => write!(f, "{non_dealer}/{dealer}"),
+        }
+    }
+}
+
+impl ShownPayment {
+    /// Check the payment is by tsumo.
+    pub fn is_tsumo(&self) -> bool {
+        !matches!(self, Self::Ron(_))
+    }
+
+    /// Get the payment the way a scoreboard shows it, failing when it is too large to count.
+    pub(crate) fn from_payment(
+        payment: &Payment,
+        dealer: bool,
+        tsumo: bool,
+        honba: HonbaCounter,
+    ) -> Result<Self, HandErr> {
+        let shown = match (dealer, tsumo) {
+            (true, false) => payment.checked_dealer_ron(honba).map(Self::Ron),
+            (false, false) => payment.checked_non_dealer_ron(honba).map(Self::Ron),
+            (true, true) => payment.checked_dealer_tsumo(honba).map(Self::TsumoAll),
+            (false, true) => payment
+                .checked_non_dealer_tsumo_to_non_dealer(honba)
+                .zip(payment.checked_non_dealer_tsumo_to_dealer(honba))
+                .map(|(non_dealer, dealer)| Self::Tsumo { non_dealer, dealer }),
+        };
+        shown.ok_or(HandErr::ScoreOverflow)
+    }
+}
+
+/// A han and fu that scores a payment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HanFu {
+    pub han: HanValue,
+    /// The least fu that scores the payment, any more fu scoring the same when it is a limit hand.
+    /// `None` when every fu does.
+    pub fu: Option<FuValue>,
+    pub limit: Option<LimitHands>,
+}
+
+/// Find every han and fu that scores a payment, the reverse of [`calculate`].
+///
+/// A limit hand reached by enough fu is listed once with the least fu it needs, and a limit hand any fu reaches without fu.
+/// A tsumo split between the non-dealers and the dealer can only be won by a non-dealer, and a tsumo paid by all only by the dealer.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::limit_hand::LimitHands;
+/// use mahc::lookup::{lookup, HanFu, ShownPayment};
+/// use mahc::ruleset::Ruleset;
+///
+/// let rules = Ruleset::default();
+/// let hands = lookup(ShownPayment::Ron(7_700), false, 0, &rules).unwrap();
+/// assert_eq!(
+///     hands,
+///     [
+///         HanFu { han: 3, fu: Some(60), limit: None },
+///         HanFu { han: 4, fu: Some(30), limit: None },
+///     ]
+/// );
+///
+/// let tsumo = "2000/4000".to_string().try_into().unwrap();
+/// let hands = lookup(tsumo, false, 0, &rules).unwrap();
+/// assert_eq!(hands[0], HanFu { han: 3, fu: Some(70), limit: Some(LimitHands::Mangan) });
+/// assert_eq!(hands[2], HanFu { han: 5, fu: None, limit: Some(LimitHands::Mangan) });
+/// ```
+pub fn lookup(
+    shown: ShownPayment,
+    dealer: bool,
+    honba: HonbaCounter,
+    rules: &Ruleset,
+) -> Result<Vec<HanFu>, HandErr> {
+    match shown {
+        ShownPayment::TsumoAll(_) if !dealer => return Err(HandErr::InvalidPayment),
+        ShownPayment::Tsumo { .. } if dealer => return Err(HandErr::InvalidPayment),
+        _ => {}
+    }
+    let tsumo = shown.is_tsumo();
+    let scores = |payment: &Payment| ShownPayment::from_payment(payment, dealer, tsumo, honba);
+
+    let mut out = vec![];
+    let mut han = 1;
+    // payments only grow with the han, so the search stops at the first han that pays too much
+    loop {
+        let fus: Vec<FuValue> = FU_VALUES
+            .into_iter()
+            .filter(|fu| is_possible_han_fu(han, *fu, tsumo))
+            .collect();
+        let mut matches = vec![];
+        let mut too_much = true;
+        for fu in &fus {
+            let Ok(payment) = calculate(han, *fu, rules) else {
+                continue;
+            };
+            let scored = scores(&payment)?;
+            if scored == shown {
+                matches.push(*fu);
+            }
+            too_much &= paid(scored) > paid(shown);
+        }
+        if too_much || (!rules.aotenjou && han > MAX_HAN) {
+            break;
+        }
+
+        let limit = matches
+            .first()
+            .and_then(|fu| LimitHands::get_limit_hand(han, *fu, rules));
+        match limit {
+            Some(_) if matches.len() == fus.len() => out.push(HanFu {
+                han,
+                fu: None,
+                limit,
+            }),
+            Some(_) => out.push(HanFu {
+                han,
+                fu: Some(matches[0]),
+                limit,
+            }),
+            None => out.extend(matches.into_iter().map(|fu| HanFu {
+                han,
+                fu: Some(fu),
+                limit,
+            })),
+        }
+        han += 1;
+    }
+
+    // without aotenjou yakuman are scored by count, not han
+    if !rules.aotenjou {
+        for count in 1..=MAX_YAKUMAN {
+            let limit = LimitHands::Yakuman(count);
+            if scores(&rules_payment(limit.get_score(), rules))? == shown {
+                out.push(HanFu {
+                    han: MAX_HAN * count,
+                    fu: None,
+                    limit: Some(limit),
+                });
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// The largest single payment shown, to compare payments of the same kind.
+fn paid(shown: ShownPayment) -> Points {
+    match shown {
+        ShownPayment::Ron(points) | ShownPayment::TsumoAll(points) => points,
+        ShownPayment::Tsumo { dealer, .. } => dealer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shown(value: &str) -> ShownPayment {
+        value.to_string().try_into().unwrap()
+    }
+
+    #[test]
+    fn shown_payments() {
+        assert_eq!(shown("7700"), ShownPayment::Ron(7_700));
+        assert_eq!(shown("3900 all"), ShownPayment::TsumoAll(3_900));
+        assert_eq!(
+            shown("2000/3900"),
+            ShownPayment::Tsumo {
+                non_dealer: 2_000,
+                dealer: 3_900
+            }
+        );
+        assert_eq!(shown("2000/3900").to_string(), "2000/3900");
+        assert_eq!(
+            ShownPayment::try_from("7700a".to_string()),
+            Err(HandErr::InvalidPayment)
+        );
+    }
+
+    #[test]
+    fn lookups() {
+        let rules = Ruleset::default();
+        let han_fu = |value: &str, dealer: bool, honba: HonbaCounter, rules: &Ruleset| {
+            lookup(shown(value), dealer, honba, rules)
+                .unwrap()
+                .iter()
+                .map(|hand| (hand.han, hand.fu))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            han_fu("2000/3900", false, 0, &rules),
+            [(3, Some(60)), (4, Some(30))]
+        );
+        assert_eq!(
+            han_fu("3900 all", true, 0, &rules),
+            [(3, Some(60)), (4, Some(30))]
+        );
+        assert_eq!(
+            han_fu("1600", false, 0, &rules),
+            [(1, Some(50)), (2, Some(25))]
+        );
+        assert_eq!(
+            han_fu("1900", false, 1, &rules),
+            [(1, Some(50)), (2, Some(25))]
+        );
+        assert_eq!(
+            han_fu("400/700", false, 0, &rules),
+            [(1, Some(40)), (2, Some(20))]
+        );
+        assert!(han_fu("7800", false, 0, &rules).is_empty());
+
+        // kiriage mangan makes 4 han 30 fu a mangan
+        let wrc = Ruleset::wrc();
+        assert_eq!(
+            lookup(shown("8000"), false, 0, &wrc).unwrap()[1],
+            HanFu {
+                han: 4,
+                fu: Some(30),
+                limit: Some(LimitHands::Mangan)
+            }
+        );
+
+        let yakuman = lookup(shown("64000"), false, 0, &rules).unwrap();
+        assert_eq!(yakuman[0].limit, Some(LimitHands::Yakuman(2)));
+
+        let aotenjou = Ruleset {
+            aotenjou: true,
+            ..Ruleset::default()
+        };
+        assert_eq!(
+            han_fu("12800", false, 0, &aotenjou),
+            [(3, Some(100)), (4, Some(50)), (5, Some(25))]
+        );
+
+        assert_eq!(
+            lookup(shown("3900 all"), false, 0, &rules),
+            Err(HandErr::InvalidPayment)
+        );
+        assert_eq!(
+            lookup(shown("2000/3900"), true, 0, &rules),
+            Err(HandErr::InvalidPayment)
+        );
+    }
+}
@@ -2,37 +2,72 @@ use std::ffi::OsString;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 
-use clap::Parser;
-use mahc::calc;
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand};
+use mahc::calc::{self, BestScore};
+use mahc::context::{CalledMeld, WinContext};
+use mahc::decompose::{decompose, decompose_hand, parse_tiles};
+use mahc::furiten::{check_furiten, hand_furiten, Furiten};
+use mahc::game::{Game, GameEnd, GameLength, GameRules, HandOutcome};
 use mahc::hand::error::HandErr;
 use mahc::hand::Hand;
+use mahc::limit_hand::LimitHands;
+use mahc::lookup::{lookup, HanFu, ShownPayment};
+use mahc::orasu::{orasu, place, Requirement, WinMethod};
 use mahc::payment::Payment;
-use mahc::score::{FuValue, HanValue, HonbaCounter, Score};
+use mahc::ruleset::{NagashiMangan, Ruleset, PRESETS};
+use mahc::ryuukyoku::{check_tenpai, exhaustive_draw, Ryuukyoku};
+use mahc::score::{FuValue, HanValue, HonbaCounter, RiichiStickCounter, Score};
+use mahc::settlement::{settle, PointDelta, Settlement, SEATS};
+use mahc::shanten::Shanten;
+use mahc::standings::{standings, Standing, TieBreak, UmaOka};
+use mahc::table::{score_table, TableRow};
+use mahc::tenpai::{tenpai_waits, wait_tiles, TenpaiWait};
 use mahc::tile_group::TileGroup;
+use mahc::ukeire::{ukeire, Discard};
 use serde_json::json;
 
 /// riichi mahjong calculator tool
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Hand tiles
     #[clap(long, value_delimiter = ' ', num_args = 1..)]
     tiles: Option<Vec<String>>,
 
+    /// ungrouped hand tiles, the groups are found automatically (eg 123456m789p11z55z)
+    #[arg(long)]
+    hand: Option<String>,
+
+    /// called melds and closed kans, used with --hand
+    #[arg(long, value_delimiter = ' ', num_args = 1..)]
+    calls: Option<Vec<String>>,
+
+    /// list every decomposition of --hand instead of scoring it
+    #[arg(long, default_value_t = false)]
+    decompose: bool,
+
     /// Winning tile
     #[arg(short, long)]
     win: Option<String>,
 
     /// Dora indicator tiles
-    #[arg(short, long, value_delimiter = ' ', num_args = 1..)]
+    #[arg(short, long, value_delimiter = ' ', num_args = 1.., global = true)]
     dora: Option<Vec<String>>,
 
+    /// Ura dora indicator tiles
+    #[arg(long, value_delimiter = ' ', num_args = 1.., global = true)]
+    ura: Option<Vec<String>>,
+
     /// seat wind
-    #[arg(short, long, default_value = "Ew")]
+    #[arg(short, long, default_value = "Ew", global = true)]
     seat: String,
 
     /// prevelant wind
-    #[arg(short, long, default_value = "Ew")]
+    #[arg(short, long, default_value = "Ew", global = true)]
     prev: String,
 
     /// is tsumo
@@ -40,7 +75,7 @@ pub struct Args {
     tsumo: bool,
 
     /// is riichi
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, default_value_t = false, global = true)]
     riichi: bool,
 
     /// is double riichi
@@ -51,8 +86,8 @@ pub struct Args {
     #[arg(short, long, default_value_t = false)]
     ippatsu: bool,
 
-    /// is haitei
-    #[arg(long, default_value_t = false)]
+    /// is the last tile (haitei on tsumo, houtei on ron)
+    #[arg(long, visible_alias = "houtei", default_value_t = false)]
     haitei: bool,
 
     /// is rinshan
@@ -67,10 +102,42 @@ pub struct Args {
     #[arg(long, default_value_t = false)]
     tenhou: bool,
 
+    /// own discards, for furiten (eg 19m4p)
+    #[arg(long, global = true)]
+    discards: Option<String>,
+
+    /// tiles passed since the last turn, or since riichi with -r, for furiten
+    #[arg(long, global = true)]
+    passed: Option<String>,
+
+    /// calls in the order they were made and the seat each came from, for pao (eg rrrdo:Sw 1111m)
+    #[arg(long, value_delimiter = ' ', num_args = 1..)]
+    called_from: Option<Vec<String>>,
+
+    /// seat that dealt in on a ron, shows each player's point change (eg Nw)
+    #[arg(long)]
+    discarder: Option<String>,
+
+    /// north tiles set aside as dora in sanma (nukidora)
+    #[arg(long, default_value_t = 0, global = true)]
+    nukidora: u32,
+
     /// honba count
-    #[arg(short, long, default_value_t = 0)]
+    #[arg(short, long, default_value_t = 0, global = true)]
     ba: HonbaCounter,
 
+    /// riichi sticks on the table, including the winner's own
+    #[arg(long, visible_alias = "kyoutaku", default_value_t = 0, global = true)]
+    riichi_sticks: RiichiStickCounter,
+
+    /// ruleset preset
+    #[arg(long, default_value = "majsoul", value_parser = PossibleValuesParser::new(PRESETS), global = true)]
+    rules: String,
+
+    /// aotenjou, no limits on the score
+    #[arg(long, default_value_t = false, global = true)]
+    aotenjou: bool,
+
     /// calculator mode
     #[arg(short, long, default_value = None, value_delimiter = ' ', num_args = 2)]
     manual: Option<Vec<u32>>,
@@ -80,7 +147,7 @@ pub struct Args {
     file: Option<String>,
 
     /// stdout as json
-    #[arg(long, default_value_t = false)]
+    #[arg(long, default_value_t = false, global = true)]
     json: bool,
 
     /// file output
@@ -88,88 +155,1249 @@ pub struct Args {
     output: Option<String>,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// shanten (tiles away from tenpai) of a hand
+    Shanten {
+        /// ungrouped closed hand tiles (eg 123456m789p1122z)
+        tiles: String,
+
+        /// called melds and closed kans
+        #[arg(long, value_delimiter = ' ', num_args = 1..)]
+        calls: Option<Vec<String>>,
+    },
+    /// tile efficiency of every discard from a 14 tile hand
+    Ukeire {
+        /// ungrouped closed hand tiles (eg 123456m789p11223z)
+        tiles: String,
+
+        /// called melds and closed kans
+        #[arg(long, value_delimiter = ' ', num_args = 1..)]
+        calls: Option<Vec<String>>,
+
+        /// other visible tiles, such as discards (eg 1m9p77z)
+        #[arg(long)]
+        visible: Option<String>,
+    },
+    /// furiten of a tenpai hand from --discards and --passed
+    Furiten {
+        /// ungrouped closed hand tiles (eg 123456m789p1122z)
+        tiles: String,
+
+        /// called melds and closed kans
+        #[arg(long, value_delimiter = ' ', num_args = 1..)]
+        calls: Option<Vec<String>>,
+    },
+    /// every tile a tenpai hand is waiting on and its score
+    Waits {
+        /// ungrouped closed hand tiles (eg 123456m789p1122z)
+        tiles: String,
+
+        /// called melds and closed kans
+        #[arg(long, value_delimiter = ' ', num_args = 1..)]
+        calls: Option<Vec<String>>,
+    },
+    /// noten payments of an exhaustive draw
+    Draw {
+        /// seats of the tenpai players, each can be checked with its hand as seat:tiles,calls (eg Sw:23m456p789s11z,rrrdo)
+        #[arg(long, value_delimiter = ' ', num_args = 1..)]
+        tenpai: Option<Vec<String>>,
+    },
+    /// nagashi mangan of the -s seat from its discards
+    Nagashi {
+        /// discards of the player (eg 19m1p9s1234z)
+        discards: String,
+
+        /// another player called one of the discards
+        #[arg(long, default_value_t = false)]
+        called: bool,
+    },
+    /// keep score of a whole game, hand by hand
+    Game {
+        #[command(subcommand)]
+        action: GameAction,
+
+        /// file the game is kept in
+        #[arg(long, default_value = "mahc-game.json")]
+        state: String,
+    },
+    /// final standings with uma and oka from the points of each player, in seat order from the first dealer
+    #[command(allow_negative_numbers = true)]
+    Standings {
+        /// points of each player at the end of the game
+        #[arg(num_args = 3..=4, required = true)]
+        points: Vec<PointDelta>,
+
+        /// uma in thousands for every place, or just the top half (eg 15 5 for +15/+5/-5/-15)
+        #[arg(long, value_delimiter = ' ', num_args = 1.., default_values_t = [15, 5])]
+        uma: Vec<PointDelta>,
+
+        /// points each player started with
+        #[arg(long, default_value_t = 25_000)]
+        starting_points: PointDelta,
+
+        /// points each player returns, the rest going to first place as oka
+        #[arg(long, default_value_t = 30_000)]
+        return_points: PointDelta,
+
+        /// placing of players tied on points
+        #[arg(long, default_value = "seat", value_parser = PossibleValuesParser::new(["seat", "split"]))]
+        tie_break: String,
+
+        /// standings format, --json also works
+        #[arg(long, default_value = "text", value_parser = PossibleValuesParser::new(["text", "csv"]))]
+        format: String,
+    },
+    /// cheapest win to move up a place in the last hand, from each player's points in seat order from the first dealer
+    #[command(allow_negative_numbers = true)]
+    Orasu {
+        /// points of each player
+        #[arg(num_args = 3..=4, required = true)]
+        points: Vec<PointDelta>,
+
+        /// player looking to move up, from 1
+        #[arg(long)]
+        player: usize,
+
+        /// dealer of the hand, from 1, the last player by default
+        #[arg(long)]
+        dealer: Option<usize>,
+    },
+    /// every han and fu that scores a payment, honba included with -b
+    Lookup {
+        /// payment as shown on a scoreboard, a ron (eg 7700) or a tsumo (eg 2000/3900 or 3900all)
+        payment: String,
+
+        /// the winner is the dealer
+        #[arg(long, default_value_t = false)]
+        dealer: bool,
+    },
+    /// table of the payments of every han and fu under the --rules ruleset, honba included with -b
+    Table {
+        /// most han listed before the limit hands
+        #[arg(long, default_value_t = 4)]
+        max_han: HanValue,
+
+        /// table format, --json also works
+        #[arg(long, default_value = "text", value_parser = PossibleValuesParser::new(["text", "md", "csv", "html"]))]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum GameAction {
+    /// start a new game with the --rules ruleset
+    New {
+        /// number of winds played
+        #[arg(long, default_value = "hanchan", value_parser = PossibleValuesParser::new(["tonpuusen", "hanchan"]))]
+        length: String,
+
+        /// points each player starts with
+        #[arg(long, default_value_t = 25_000)]
+        starting_points: PointDelta,
+
+        /// play on below zero points
+        #[arg(long, default_value_t = false)]
+        no_tobi: bool,
+
+        /// end the game when the dealer wins the last round in first place
+        #[arg(long, default_value_t = false)]
+        agari_yame: bool,
+
+        /// end the game when the dealer is tenpai at a draw of the last round in first place
+        #[arg(long, default_value_t = false)]
+        tenpai_yame: bool,
+    },
+    /// put down a riichi stick for a seat
+    Riichi {
+        /// seat wind of the player (eg Sw)
+        seat: String,
+    },
+    /// a win by the -s seat, by tsumo unless a --discarder is given
+    Win {
+        /// ungrouped closed hand tiles (eg 123456m789p11z55z)
+        tiles: String,
+
+        /// called melds and closed kans
+        #[arg(long, value_delimiter = ' ', num_args = 1..)]
+        calls: Option<Vec<String>>,
+
+        /// winning tile
+        #[arg(short, long)]
+        win: String,
+
+        /// seat that dealt in
+        #[arg(long)]
+        discarder: Option<String>,
+    },
+    /// the wall ran out
+    Draw {
+        /// seats of the tenpai players, as in mahc draw
+        #[arg(long, value_delimiter = ' ', num_args = 1..)]
+        tenpai: Option<Vec<String>>,
+    },
+    /// the wall ran out and the -s seat had nagashi mangan
+    Nagashi {
+        /// discards of the player (eg 19m1p9s1234z)
+        discards: String,
+
+        /// another player called one of the discards
+        #[arg(long, default_value_t = false)]
+        called: bool,
+
+        /// seats of the tenpai players, as in mahc draw
+        #[arg(long, value_delimiter = ' ', num_args = 1..)]
+        tenpai: Option<Vec<String>>,
+    },
+    /// the hand was called off
+    Abort,
+    /// show the game
+    Show,
+}
+
 pub fn parse_calculator(args: &Args) -> Result<String, HandErr> {
     let honba = args.ba;
     let han = args.manual.as_ref().unwrap()[0];
     let fu = args.manual.as_ref().unwrap()[1].into();
-    let payment = calc::calculate(han, fu)?;
+    let rules = ruleset(args);
+    let payment = calc::calculate(han, fu, &rules)?;
+    if !payment.is_countable(honba) {
+        return Err(HandErr::ScoreOverflow);
+    }
+    let limit = LimitHands::get_limit_hand(han, fu, &rules);
 
     if args.json {
-        Ok(json_calc_out(&payment, honba, han, fu))
+        Ok(json_calc_out(&payment, honba, han, fu, limit))
     } else {
-        Ok(default_calc_out(&payment, honba, han, fu))
+        Ok(default_calc_out(&payment, honba, han, fu, limit))
     }
 }
 
-pub fn parse_hand(args: &Args) -> Result<String, HandErr> {
-    if args.tiles.is_none() {
+pub fn parse_decompositions(args: &Args) -> Result<String, HandErr> {
+    let Some(tiles) = &args.hand else {
         return Err(HandErr::NoHandTiles);
-    }
-    if args.win.is_none() {
+    };
+    let Some(win) = &args.win else {
         return Err(HandErr::NoWinTile);
+    };
+    let closed = parse_tiles(tiles)?;
+    let mut calls: Vec<TileGroup> = vec![];
+    for call in args.calls.clone().unwrap_or_default() {
+        calls.push(call.try_into()?);
+    }
+    let win_tile: TileGroup = win.clone().try_into()?;
+    let decompositions: Vec<Vec<String>> = decompose(&closed, &calls, &win_tile)?
+        .iter()
+        .map(|groups| groups_to_strings(groups))
+        .collect();
+
+    if args.json {
+        Ok(json!({ "decompositions": decompositions }).to_string())
+    } else {
+        Ok(decompositions
+            .iter()
+            .map(|groups| format!("\n{}", groups.join(" ")))
+            .collect())
     }
-    if args.tsumo && args.chankan {
-        return Err(HandErr::ChankanTsumo);
+}
+
+pub fn parse_shanten(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Shanten { tiles, calls }) = &args.command else {
+        return Err(HandErr::NoHandTiles);
+    };
+    let closed = parse_tiles(tiles)?;
+    let mut called: Vec<TileGroup> = vec![];
+    for call in calls.clone().unwrap_or_default() {
+        called.push(call.try_into()?);
     }
-    if args.rinshan && (!args.tsumo) {
-        return Err(HandErr::RinshanWithoutTsumo);
+    let shanten = Shanten::new(&closed, &called)?;
+
+    if args.json {
+        Ok(json_shanten_out(&shanten))
+    } else {
+        Ok(default_shanten_out(&shanten))
     }
-    if args.rinshan && args.ippatsu {
-        return Err(HandErr::RinshanIppatsu);
+}
+
+pub fn parse_furiten(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Furiten { tiles, calls }) = &args.command else {
+        return Err(HandErr::NoHandTiles);
+    };
+    let closed = parse_tiles(tiles)?;
+    let mut called: Vec<TileGroup> = vec![];
+    for call in calls.clone().unwrap_or_default() {
+        called.push(call.try_into()?);
     }
-    if args.riichi && args.doubleriichi {
-        return Err(HandErr::DuplicateRiichi);
+    let waits = wait_tiles(&closed, &called)?;
+    if waits.is_empty() {
+        return Err(HandErr::NotTenpai);
     }
-    if args.ippatsu && !(args.riichi || args.doubleriichi) {
-        return Err(HandErr::IppatsuWithoutRiichi);
+    let furiten = check_furiten(
+        &waits,
+        &optional_tiles(&args.discards)?,
+        &optional_tiles(&args.passed)?,
+        args.riichi,
+    );
+
+    if args.json {
+        Ok(json_furiten_out(&waits, &furiten))
+    } else {
+        Ok(default_furiten_out(&waits, &furiten))
     }
-    if args.doubleriichi && args.ippatsu && args.haitei {
-        return Err(HandErr::DoubleRiichiHaiteiIppatsu);
+}
+
+/// Build the win context from the flags.
+fn win_context(args: &Args) -> Result<WinContext, HandErr> {
+    let indicators = |tiles: &Option<Vec<String>>| -> Vec<TileGroup> {
+        tiles
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|tile| tile.try_into().ok())
+            .collect()
+    };
+
+    WinContext::builder()
+        .tsumo(args.tsumo)
+        .riichi(args.riichi)
+        .doubleriichi(args.doubleriichi)
+        .ippatsu(args.ippatsu)
+        .last_tile(args.haitei)
+        .rinshan(args.rinshan)
+        .chankan(args.chankan)
+        .tenhou(args.tenhou)
+        .dora(indicators(&args.dora))
+        .ura_dora(indicators(&args.ura))
+        .nukidora(args.nukidora)
+        .called_from(called_from(args)?)
+        .honba(args.ba)
+        .riichi_sticks(args.riichi_sticks)
+        .build()
+}
+
+/// The ruleset picked with --rules, the value is checked by clap.
+fn ruleset(args: &Args) -> Ruleset {
+    let rules = Ruleset::preset(&args.rules).unwrap_or_default();
+    Ruleset {
+        aotenjou: rules.aotenjou || args.aotenjou,
+        ..rules
     }
-    if args.doubleriichi && args.haitei && args.chankan {
-        return Err(HandErr::DoubleRiichiHaiteiChankan);
+}
+
+/// The calls given with --called-from, as meld:seat or just the meld for a closed kan.
+fn called_from(args: &Args) -> Result<Vec<CalledMeld>, HandErr> {
+    let mut calls = vec![];
+    for call in args.called_from.clone().unwrap_or_default() {
+        let (meld, from) = match call.split_once(':') {
+            Some((meld, from)) => (meld.to_string(), Some(from.to_string().try_into()?)),
+            None => (call, None),
+        };
+        calls.push(CalledMeld {
+            meld: meld.try_into()?,
+            from,
+        });
     }
-    let hand = Hand::new_from_strings(
-        args.tiles.clone().unwrap(),
-        args.win.clone().unwrap(),
+    Ok(calls)
+}
+
+/// Reject a ron win when the winner is furiten.
+fn check_ron_furiten(args: &Args, hand: &Hand) -> Result<(), HandErr> {
+    if args.tsumo || (args.discards.is_none() && args.passed.is_none()) {
+        return Ok(());
+    }
+    let furiten = hand_furiten(
+        hand,
+        &optional_tiles(&args.discards)?,
+        &optional_tiles(&args.passed)?,
+        args.riichi || args.doubleriichi,
+    )?;
+    if !furiten.is_empty() {
+        return Err(HandErr::FuritenRon);
+    }
+    Ok(())
+}
+
+fn optional_tiles(tiles: &Option<String>) -> Result<Vec<TileGroup>, HandErr> {
+    match tiles {
+        Some(tiles) => parse_tiles(tiles),
+        None => Ok(vec![]),
+    }
+}
+
+pub fn parse_ukeire(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Ukeire {
+        tiles,
+        calls,
+        visible,
+    }) = &args.command
+    else {
+        return Err(HandErr::NoHandTiles);
+    };
+    let closed = parse_tiles(tiles)?;
+    let mut called: Vec<TileGroup> = vec![];
+    for call in calls.clone().unwrap_or_default() {
+        called.push(call.try_into()?);
+    }
+    let mut doras: Vec<TileGroup> = vec![];
+    for dora in args.dora.clone().unwrap_or_default() {
+        doras.push(dora.try_into()?);
+    }
+    let visible = match visible {
+        Some(visible) => parse_tiles(visible)?,
+        None => vec![],
+    };
+    let discards = ukeire(&closed, &called, &doras, &visible)?;
+
+    if args.json {
+        Ok(json_ukeire_out(&discards))
+    } else {
+        Ok(default_ukeire_out(&discards))
+    }
+}
+
+pub fn parse_waits(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Waits { tiles, calls }) = &args.command else {
+        return Err(HandErr::NoHandTiles);
+    };
+    let waits = tenpai_waits(
+        tiles,
+        calls.clone().unwrap_or_default(),
         args.prev.clone(),
         args.seat.clone(),
+        &win_context(args)?,
+        &ruleset(args),
     )?;
-    let doras: Option<Vec<TileGroup>> = args.dora.clone().map(|dora_tiles| {
-        dora_tiles
-            .into_iter()
-            .filter_map(|tile| tile.try_into().ok())
-            .collect()
+
+    if args.json {
+        Ok(json_waits_out(&waits))
+    } else {
+        Ok(default_waits_out(&waits))
+    }
+}
+
+pub fn parse_draw(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Draw { tenpai }) = &args.command else {
+        return Err(HandErr::NoHandTiles);
+    };
+    let tenpai = tenpai_seats(tenpai)?;
+    let draw = exhaustive_draw(&tenpai, args.ba, &ruleset(args))?;
+
+    if args.json {
+        Ok(json_draw_out(&tenpai, &draw))
+    } else {
+        Ok(default_draw_out(&tenpai, &draw))
+    }
+}
+
+/// The seats given with --tenpai, a seat given with its hand as seat:tiles,calls is checked to be tenpai.
+fn tenpai_seats(tenpai: &Option<Vec<String>>) -> Result<Vec<TileGroup>, HandErr> {
+    let mut seats = vec![];
+    for entry in tenpai.clone().unwrap_or_default() {
+        let Some((seat, hand)) = entry.split_once(':') else {
+            seats.push(entry.try_into()?);
+            continue;
+        };
+        let mut groups = hand.split(',');
+        let closed = parse_tiles(groups.next().unwrap_or_default())?;
+        let mut calls: Vec<TileGroup> = vec![];
+        for call in groups {
+            calls.push(call.to_string().try_into()?);
+        }
+        check_tenpai(&closed, &calls)?;
+        seats.push(seat.to_string().try_into()?);
+    }
+    Ok(seats)
+}
+
+pub fn json_draw_out(tenpai: &[TileGroup], draw: &Ryuukyoku) -> String {
+    let payments: serde_json::Map<String, serde_json::Value> = SEATS
+        .iter()
+        .zip(&draw.deltas)
+        .map(|(seat, delta)| (seat.to_string(), json!(delta)))
+        .collect();
+    let out = json!({
+        "tenpai" : groups_to_strings(tenpai),
+        "payments" : payments,
+        "renchan" : draw.dealer_stays,
+        "honba" : draw.honba,
     });
-    let score = calc::get_hand_score(
-        hand,
-        doras,
-        args.tsumo,
-        args.riichi,
-        args.doubleriichi,
-        args.ippatsu,
-        args.haitei,
-        args.rinshan,
-        args.chankan,
-        args.tenhou,
+    out.to_string()
+}
+
+pub fn default_draw_out(tenpai: &[TileGroup], draw: &Ryuukyoku) -> String {
+    let mut out = format!("\nTenpai: {}", groups_to_strings(tenpai).join(" "));
+    out.push_str("\nNoten Payments: ");
+    for (seat, delta) in SEATS.iter().zip(&draw.deltas) {
+        match delta {
+            0 => out.push_str(&format!("\n  {seat}: 0")),
+            delta => out.push_str(&format!("\n  {seat}: {delta:+}")),
+        }
+    }
+    out.push_str(if draw.dealer_stays {
+        "\nDealer: Renchan"
+    } else {
+        "\nDealer: Passes"
+    });
+    out.push_str(&format!("\nHonba: {}", draw.honba));
+    out
+}
+
+pub fn parse_nagashi(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Nagashi { discards, called }) = &args.command else {
+        return Err(HandErr::NotNagashiMangan);
+    };
+    let rules = ruleset(args);
+    let context = win_context(args)?.to_builder().tsumo(true).build()?;
+    let score = calc::nagashi_mangan(&parse_tiles(discards)?, *called, &context, &rules)?;
+    let settlement = settle(&score, &args.seat.clone().try_into()?, None, &rules)?;
+    let kind = match rules.nagashi_mangan {
+        Some(NagashiMangan::Win) => "Win",
+        _ => "Draw",
+    };
+
+    if args.json {
+        let mut out = json_hand_value(&score);
+        out["nagashi"] = json!(kind);
+        out["settlement"] = json_settlement_value(&settlement);
+        Ok(out.to_string())
+    } else {
+        Ok(format!(
+            "\nNagashi Mangan ({kind})\nDealer: {}\nNon-dealer: {}{}",
+            payment_to_string(&score, true, true),
+            payment_to_string(&score, false, true),
+            settlement_to_string(Some(&settlement))
+        ))
+    }
+}
+
+pub fn parse_game(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Game { action, state }) = &args.command else {
+        return Err(HandErr::NoGame);
+    };
+    if let GameAction::New {
+        length,
+        starting_points,
+        no_tobi,
+        agari_yame,
+        tenpai_yame,
+    } = action
+    {
+        let game_rules = GameRules {
+            length: if length == "tonpuusen" {
+                GameLength::Tonpuusen
+            } else {
+                GameLength::Hanchan
+            },
+            starting_points: *starting_points,
+            tobi: !no_tobi,
+            agari_yame: *agari_yame,
+            tenpai_yame: *tenpai_yame,
+        };
+        let game = Game::new(game_rules, ruleset(args));
+        save_game(state, &game, &args.rules)?;
+        return Ok(game_out(&game, None, args.json));
+    }
+
+    let saved = load_game(state)?;
+    let mut game = saved.game;
+    let deltas = match action {
+        GameAction::New { .. } | GameAction::Show => None,
+        GameAction::Riichi { seat } => {
+            game.declare_riichi(&seat.clone().try_into()?)?;
+            None
+        }
+        GameAction::Win {
+            tiles,
+            calls,
+            win,
+            discarder,
+        } => {
+            let hands = decompose_hand(
+                tiles,
+                calls.clone().unwrap_or_default(),
+                win.clone(),
+                game.round_wind().to_string(),
+                args.seat.clone(),
+            )?;
+            let context = game
+                .win_context(&args.seat.clone().try_into()?, &win_context(args)?)?
+                .to_builder()
+                .tsumo(discarder.is_none())
+                .build()?;
+            let hand = calc::get_best_hand_score(hands, &context, &game.rules)?.hand;
+            let outcome = match discarder {
+                Some(discarder) => HandOutcome::Ron {
+                    wins: vec![(hand, context)],
+                    discarder: discarder.clone().try_into()?,
+                },
+                None => HandOutcome::Tsumo(hand, context),
+            };
+            Some(game.record(outcome)?)
+        }
+        GameAction::Draw { tenpai } => {
+            let tenpai = tenpai_seats(tenpai)?;
+            Some(game.record(HandOutcome::ExhaustiveDraw { tenpai })?)
+        }
+        GameAction::Nagashi {
+            discards,
+            called,
+            tenpai,
+        } => {
+            let outcome = HandOutcome::NagashiMangan {
+                seat: args.seat.clone().try_into()?,
+                discards: parse_tiles(discards)?,
+                called: *called,
+                tenpai: tenpai_seats(tenpai)?,
+            };
+            Some(game.record(outcome)?)
+        }
+        GameAction::Abort => Some(game.record(HandOutcome::AbortiveDraw)?),
+    };
+
+    save_game(state, &game, &saved.rules)?;
+    Ok(game_out(&game, deltas.as_deref(), args.json))
+}
+
+/// A game read from its file, with the name of its ruleset.
+struct SavedGame {
+    game: Game,
+    rules: String,
+}
+
+/// Write the game to its file, `rules` is the name of the preset it is scored with.
+fn save_game(path: &str, game: &Game, rules: &str) -> Result<(), HandErr> {
+    let out = json!({
+        "rules" : rules,
+        "aotenjou" : game.rules.aotenjou,
+        "length" : match game.game_rules.length {
+            GameLength::Tonpuusen => "tonpuusen",
+            GameLength::Hanchan => "hanchan",
+        },
+        "startingPoints" : game.game_rules.starting_points,
+        "tobi" : game.game_rules.tobi,
+        "agariYame" : game.game_rules.agari_yame,
+        "tenpaiYame" : game.game_rules.tenpai_yame,
+        "points" : game.points,
+        "round" : game.round,
+        "honba" : game.honba,
+        "riichiSticks" : game.riichi_sticks,
+        "riichi" : game.riichi,
+        "renchan" : game.renchan,
+        "end" : game.end.map(|end| end.to_string()),
+    });
+    fs::write(path, out.to_string()).map_err(|_| HandErr::GameNotSaved)
+}
+
+fn load_game(path: &str) -> Result<SavedGame, HandErr> {
+    let contents = fs::read_to_string(path).map_err(|_| HandErr::NoGame)?;
+    let saved: serde_json::Value = serde_json::from_str(&contents).map_err(|_| HandErr::NoGame)?;
+    let number = |key: &str| saved[key].as_u64().ok_or(HandErr::NoGame);
+    let flag = |key: &str| saved[key].as_bool().ok_or(HandErr::NoGame);
+
+    let rules_name = saved["rules"].as_str().ok_or(HandErr::NoGame)?;
+    let rules = Ruleset {
+        aotenjou: flag("aotenjou")?,
+        ..Ruleset::preset(rules_name).ok_or(HandErr::NoGame)?
+    };
+    let game_rules = GameRules {
+        length: match saved["length"].as_str() {
+            Some("tonpuusen") => GameLength::Tonpuusen,
+            Some("hanchan") => GameLength::Hanchan,
+            _ => return Err(HandErr::NoGame),
+        },
+        starting_points: saved["startingPoints"].as_i64().ok_or(HandErr::NoGame)?,
+        tobi: flag("tobi")?,
+        agari_yame: flag("agariYame")?,
+        tenpai_yame: flag("tenpaiYame")?,
+    };
+    let mut game = Game::new(game_rules, rules);
+    let points: Option<Vec<PointDelta>> = saved["points"]
+        .as_array()
+        .map(|points| points.iter().filter_map(|points| points.as_i64()).collect());
+    match points {
+        Some(points) if points.len() == game.players() => game.points = points,
+        _ => return Err(HandErr::NoGame),
+    }
+    game.round = number("round")? as usize;
+    game.honba = number("honba")?;
+    game.riichi_sticks = number("riichiSticks")?;
+    // games saved before riichi declarations were kept have none
+    for player in saved["riichi"].as_array().into_iter().flatten() {
+        match player.as_u64() {
+            Some(player) if (player as usize) < game.players() => game.riichi.push(player as usize),
+            _ => return Err(HandErr::NoGame),
+        }
+    }
+    game.renchan = number("renchan")? as u32;
+    game.end = [
+        GameEnd::Tobi,
+        GameEnd::LastRound,
+        GameEnd::AgariYame,
+        GameEnd::TenpaiYame,
+    ]
+    .into_iter()
+    .find(|end| saved["end"].as_str() == Some(&end.to_string()));
+
+    Ok(SavedGame {
+        game,
+        rules: rules_name.to_string(),
+    })
+}
+
+fn wind_name(wind: &TileGroup) -> &'static str {
+    match wind.to_string().as_str() {
+        "Ew" => "East",
+        "Sw" => "South",
+        "Ww" => "West",
+        _ => "North",
+    }
+}
+
+/// The round and every player's points, with the change from the last hand when there is one.
+fn game_out(game: &Game, deltas: Option<&[PointDelta]>, json: bool) -> String {
+    if json {
+        let players: Vec<serde_json::Value> = (0..game.players())
+            .map(|player| {
+                json!({
+                    "seat" : game.seat(player).to_string(),
+                    "points" : game.points[player],
+                    "delta" : deltas.map(|deltas| deltas[player]),
+                })
+            })
+            .collect();
+        return json!({
+            "wind" : game.round_wind().to_string(),
+            "hand" : game.round_number(),
+            "honba" : game.honba,
+            "riichiSticks" : game.riichi_sticks,
+            "renchan" : game.renchan,
+            "players" : players,
+            "end" : game.end.map(|end| end.to_string()),
+        })
+        .to_string();
+    }
+
+    let mut out = format!(
+        "\n{} {}/ {} Honba/ {} Riichi Sticks",
+        wind_name(&game.round_wind()),
+        game.round_number(),
+        game.honba,
+        game.riichi_sticks
+    );
+    for player in 0..game.players() {
+        out.push_str(&format!(
+            "\n  Player {} ({}): {}",
+            player + 1,
+            game.seat(player),
+            game.points[player]
+        ));
+        match deltas.map(|deltas| deltas[player]) {
+            None | Some(0) => {}
+            Some(delta) => out.push_str(&format!(" ({delta:+})")),
+        }
+    }
+    if let Some(end) = game.end {
+        out.push_str(&format!("\nGame Over: {end}"));
+    }
+    out
+}
+
+pub fn parse_standings(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Standings {
+        points,
+        uma,
+        starting_points,
+        return_points,
+        tie_break,
+        format,
+    }) = &args.command
+    else {
+        return Err(HandErr::InvalidUma);
+    };
+    let uma: Vec<PointDelta> = uma.iter().map(|uma| uma * 1_000).collect();
+    let uma_oka = if uma.len() == points.len() {
+        UmaOka {
+            uma,
+            starting_points: *starting_points,
+            return_points: *return_points,
+        }
+    } else {
+        UmaOka::symmetric(&uma, points.len(), *starting_points, *return_points)?
+    };
+    let tie_break = match tie_break.as_str() {
+        "split" => TieBreak::Split,
+        _ => TieBreak::SeatOrder,
+    };
+    let standings = standings(points, &uma_oka, tie_break)?;
+
+    if args.json {
+        Ok(json_standings_out(&standings, &uma_oka))
+    } else if format == "csv" {
+        Ok(csv_standings_out(&standings))
+    } else {
+        Ok(default_standings_out(&standings, &uma_oka))
+    }
+}
+
+/// A final score in points as thousands, the way it is usually written down (eg +47.1).
+fn thousands(score: PointDelta) -> String {
+    format!("{:+.1}", score as f64 / 1_000.0)
+}
+
+pub fn json_standings_out(standings: &[Standing], uma_oka: &UmaOka) -> String {
+    let standings: Vec<serde_json::Value> = standings
+        .iter()
+        .map(|standing| {
+            json!({
+                "place" : standing.place,
+                "player" : standing.player + 1,
+                "points" : standing.points,
+                "score" : standing.score as f64 / 1_000.0,
+            })
+        })
+        .collect();
+    json!({
+        "uma" : uma_oka.uma,
+        "oka" : uma_oka.oka(),
+        "standings" : standings,
+    })
+    .to_string()
+}
+
+pub fn csv_standings_out(standings: &[Standing]) -> String {
+    let mut out = "place,player,points,score".to_string();
+    for standing in standings {
+        out.push_str(&format!(
+            "\n{},{},{},{:.1}",
+            standing.place,
+            standing.player + 1,
+            standing.points,
+            standing.score as f64 / 1_000.0
+        ));
+    }
+    out
+}
+
+pub fn default_standings_out(standings: &[Standing], uma_oka: &UmaOka) -> String {
+    let uma: Vec<String> = uma_oka
+        .uma
+        .iter()
+        .map(|uma| format!("{:+}", uma / 1_000))
+        .collect();
+    let mut out = format!(
+        "\nUma: {}/ Oka: {}",
+        uma.join("/"),
+        thousands(uma_oka.oka())
+    );
+    for standing in standings {
+        out.push_str(&format!(
+            "\n  {}. Player {}: {} ({})",
+            standing.place,
+            standing.player + 1,
+            standing.points,
+            thousands(standing.score)
+        ));
+    }
+    out
+}
+
+pub fn parse_orasu(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Orasu {
+        points,
+        player,
+        dealer,
+    }) = &args.command
+    else {
+        return Err(HandErr::InvalidPlayer);
+    };
+    let dealer = dealer.unwrap_or(points.len());
+    if *player == 0 || dealer == 0 {
+        return Err(HandErr::InvalidPlayer);
+    }
+    let (player, dealer) = (player - 1, dealer - 1);
+    let needs = orasu(
+        points,
+        player,
+        dealer,
         args.ba,
+        args.riichi_sticks,
+        &ruleset(args),
     )?;
+    let place = place(points, player);
+
+    if args.json {
+        Ok(json_orasu_out(player, place, &needs))
+    } else {
+        Ok(default_orasu_out(player, points[player], place, &needs))
+    }
+}
+
+/// A place as an ordinal number (eg 2nd).
+fn ordinal(place: usize) -> String {
+    match place {
+        1 => "1st".to_string(),
+        2 => "2nd".to_string(),
+        3 => "3rd".to_string(),
+        place => format!("{place}th"),
+    }
+}
+
+pub fn json_orasu_out(player: usize, place: usize, needs: &[Requirement]) -> String {
+    let needs: Vec<serde_json::Value> = needs
+        .iter()
+        .map(|need| {
+            json!({
+                "method" : match need.method {
+                    WinMethod::Ron(_) => "ron",
+                    WinMethod::Tsumo => "tsumo",
+                },
+                "discarder" : match need.method {
+                    WinMethod::Ron(discarder) => Some(discarder + 1),
+                    WinMethod::Tsumo => None,
+                },
+                "place" : need.place,
+                "han" : need.win.map(|win| win.han),
+                "fu" : need.win.map(|win| win.fu),
+                "limit" : need.win.and_then(|win| win.limit).map(|limit| limit.to_string()),
+                "points" : need.win.map(|win| win.points),
+            })
+        })
+        .collect();
+    json!({
+        "player" : player + 1,
+        "place" : place,
+        "requirements" : needs,
+    })
+    .to_string()
+}
+
+pub fn default_orasu_out(
+    player: usize,
+    points: PointDelta,
+    place: usize,
+    needs: &[Requirement],
+) -> String {
+    let mut out = format!("\nPlayer {}: {} ({})", player + 1, points, ordinal(place));
+    if needs.is_empty() {
+        out.push_str("\nAlready in 1st");
+        return out;
+    }
+    let mut method = None;
+    for need in needs {
+        if method != Some(need.method) {
+            method = Some(need.method);
+            match need.method {
+                WinMethod::Ron(discarder) => {
+                    out.push_str(&format!("\nRon off Player {}:", discarder + 1))
+                }
+                WinMethod::Tsumo => out.push_str("\nTsumo:"),
+            }
+        }
+        let win = match need.win {
+            None => "Not Possible".to_string(),
+            Some(win) => match win.limit {
+                Some(limit) => format!("{limit} ({})", win.points),
+                None => format!("{} Han/ {} Fu ({})", win.han, win.fu, win.points),
+            },
+        };
+        out.push_str(&format!("\n  {}: {win}", ordinal(need.place)));
+    }
+    out
+}
+
+pub fn parse_lookup(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Lookup { payment, dealer }) = &args.command else {
+        return Err(HandErr::InvalidPayment);
+    };
+    let shown: ShownPayment = payment.clone().try_into()?;
+    let hands = lookup(shown, *dealer, args.ba, &ruleset(args))?;
+
+    if args.json {
+        Ok(json_lookup_out(shown, *dealer, args.ba, &hands))
+    } else {
+        Ok(default_lookup_out(shown, *dealer, args.ba, &hands))
+    }
+}
+
+pub fn json_lookup_out(
+    shown: ShownPayment,
+    dealer: bool,
+    honba: HonbaCounter,
+    hands: &[HanFu],
+) -> String {
+    let hands: Vec<serde_json::Value> = hands
+        .iter()
+        .map(|hand| {
+            json!({
+                "han" : hand.han,
+                "fu" : hand.fu,
+                "limit" : hand.limit.map(|limit| limit.to_string()),
+            })
+        })
+        .collect();
+    json!({
+        "payment" : shown.to_string(),
+        "dealer" : dealer,
+        "tsumo" : shown.is_tsumo(),
+        "honba" : honba,
+        "hands" : hands,
+    })
+    .to_string()
+}
+
+pub fn default_lookup_out(
+    shown: ShownPayment,
+    dealer: bool,
+    honba: HonbaCounter,
+    hands: &[HanFu],
+) -> String {
+    let mut out = format!("\n{shown}");
+    if honba != 0 {
+        out.push_str(&format!("/ {honba} Honba"));
+    }
+    out.push_str(&format!(
+        " ({} {})",
+        if dealer { "Dealer" } else { "Non-dealer" },
+        if shown.is_tsumo() { "Tsumo" } else { "Ron" }
+    ));
+    if hands.is_empty() {
+        out.push_str("\n  No Han/Fu found");
+    }
+    for hand in hands {
+        let han_fu = match (hand.fu, hand.limit) {
+            (_, Some(LimitHands::Yakuman(count))) => LimitHands::Yakuman(count).to_string(),
+            (None, Some(limit)) => format!("{} Han ({limit})", hand.han),
+            (Some(fu), Some(limit)) => format!("{} Han/ {fu}+ Fu ({limit})", hand.han),
+            (Some(fu), None) => format!("{} Han/ {fu} Fu", hand.han),
+            (None, None) => format!("{} Han", hand.han),
+        };
+        out.push_str(&format!("\n  {han_fu}"));
+    }
+    out
+}
+
+pub fn parse_table(args: &Args) -> Result<String, HandErr> {
+    let Some(Command::Table { max_han, format }) = &args.command else {
+        return Err(HandErr::NoHan);
+    };
+    if *max_han == 0 {
+        return Err(HandErr::NoHan);
+    }
+    let table = score_table(*max_han, args.ba, &ruleset(args))?;
+
+    if args.json {
+        return Ok(json_table_out(&table, args.ba));
+    }
+    let header = [
+        "Han",
+        "Fu",
+        "Dealer Ron",
+        "Dealer Tsumo",
+        "Non-dealer Ron",
+        "Non-dealer Tsumo",
+    ];
+    let rows: Vec<Vec<String>> = table.iter().map(table_row_cells).collect();
+    Ok(match format.as_str() {
+        "md" => md_table_out(&header, &rows),
+        "csv" => csv_table_out(&header, &rows),
+        "html" => html_table_out(&header, &rows),
+        _ => default_table_out(&header, &rows, args.ba),
+    })
+}
+
+/// The cells of a table row, the limit hand in place of the fu when any fu scores it.
+fn table_row_cells(row: &TableRow) -> Vec<String> {
+    let fu = match (row.fu, row.limit) {
+        (Some(fu), _) => fu.to_string(),
+        (None, Some(limit)) => limit.to_string(),
+        (None, None) => "-".to_string(),
+    };
+    let payments = [
+        row.dealer_ron,
+        row.dealer_tsumo,
+        row.non_dealer_ron,
+        row.non_dealer_tsumo,
+    ]
+    .map(|shown| shown.map_or("-".to_string(), |shown| shown.to_string()));
+    let mut cells = vec![row.han.to_string(), fu];
+    cells.extend(payments);
+    cells
+}
+
+pub fn json_table_out(table: &[TableRow], honba: HonbaCounter) -> String {
+    let points = |shown: Option<ShownPayment>| match shown {
+        None => json!(null),
+        Some(ShownPayment::Ron(points)) | Some(ShownPayment::TsumoAll(points)) => json!(points),
+        Some(ShownPayment::Tsumo { non_dealer, dealer }) => {
+            json!({ "nonDealer" : non_dealer, "dealer" : dealer })
+        }
+    };
+    let rows: Vec<serde_json::Value> = table
+        .iter()
+        .map(|row| {
+            json!({
+                "han" : row.han,
+                "fu" : row.fu,
+                "limit" : row.limit.map(|limit| limit.to_string()),
+                "dealerRon" : points(row.dealer_ron),
+                "dealerTsumo" : points(row.dealer_tsumo),
+                "nonDealerRon" : points(row.non_dealer_ron),
+                "nonDealerTsumo" : points(row.non_dealer_tsumo),
+            })
+        })
+        .collect();
+    json!({
+        "honba" : honba,
+        "rows" : rows,
+    })
+    .to_string()
+}
+
+pub fn default_table_out(header: &[&str], rows: &[Vec<String>], honba: HonbaCounter) -> String {
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([name.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = String::new();
+    if honba != 0 {
+        out.push_str(&format!("\n{honba} Honba"));
+    }
+    out.push_str(&format!("\n{}", line(header.to_vec())));
+    for row in rows {
+        out.push_str(&format!(
+            "\n{}",
+            line(row.iter().map(String::as_str).collect())
+        ));
+    }
+    out
+}
+
+pub fn md_table_out(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut out = format!("| {} |", header.join(" | "));
+    out.push_str(&format!("\n|{}", "---|".repeat(header.len())));
+    for row in rows {
+        out.push_str(&format!("\n| {} |", row.join(" | ")));
+    }
+    out
+}
+
+pub fn csv_table_out(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut out = header.join(",");
+    for row in rows {
+        out.push_str(&format!("\n{}", row.join(",")));
+    }
+    out
+}
+
+pub fn html_table_out(header: &[&str], rows: &[Vec<String>]) -> String {
+    let cells = |tag: &str, cells: Vec<&str>| {
+        cells
+            .iter()
+            .map(|cell| format!("<{tag}>{cell}</{tag}>"))
+            .collect::<String>()
+    };
+    let mut out = "<table>\n  <thead>".to_string();
+    out.push_str(&format!("\n    <tr>{}</tr>", cells("th", header.to_vec())));
+    out.push_str("\n  </thead>\n  <tbody>");
+    for row in rows {
+        out.push_str(&format!(
+            "\n    <tr>{}</tr>",
+            cells("td", row.iter().map(String::as_str).collect())
+        ));
+    }
+    out.push_str("\n  </tbody>\n</table>");
+    out
+}
+
+pub fn parse_hand(args: &Args) -> Result<String, HandErr> {
+    if args.tiles.is_none() && args.hand.is_none() {
+        return Err(HandErr::NoHandTiles);
+    }
+    if args.win.is_none() {
+        return Err(HandErr::NoWinTile);
+    }
+    let context = win_context(args)?;
+
+    if let Some(tiles) = &args.hand {
+        let hands = decompose_hand(
+            tiles,
+            args.calls.clone().unwrap_or_default(),
+            args.win.clone().unwrap(),
+            args.prev.clone(),
+            args.seat.clone(),
+        )?;
+        check_ron_furiten(args, &hands[0])?;
+        let best = calc::get_best_hand_score(hands, &context, &ruleset(args))?;
+        let settlement = settlement(args, &best.score)?;
+
+        let printout = if args.json {
+            json_best_out(&best, settlement.as_ref())
+        } else {
+            default_best_out(&best) + &settlement_to_string(settlement.as_ref())
+        };
+        return Ok(printout);
+    }
 
-    //TODO VALIDATION (i dont care enough yet)
+    let hand = Hand::new_from_strings(
+        args.tiles.clone().unwrap(),
+        args.win.clone().unwrap(),
+        args.prev.clone(),
+        args.seat.clone(),
+    )?;
+    check_ron_furiten(args, &hand)?;
+    let score = calc::get_hand_score(hand, &context, &ruleset(args))?;
+    let settlement = settlement(args, &score)?;
 
     let printout = if args.json {
-        json_hand_out(&score)
+        json_hand_out(&score, settlement.as_ref())
     } else {
-        default_hand_out(&score)
+        default_hand_out(&score) + &settlement_to_string(settlement.as_ref())
     };
     Ok(printout)
 }
 
-pub fn json_calc_out(payment: &Payment, honba: HonbaCounter, han: HanValue, fu: FuValue) -> String {
+/// The point change of each player when the win is a tsumo or the --discarder is known.
+fn settlement(args: &Args, score: &Score) -> Result<Option<Settlement>, HandErr> {
+    let winner: TileGroup = args.seat.clone().try_into()?;
+    let loser: Option<TileGroup> = match &args.discarder {
+        Some(seat) if !args.tsumo => Some(seat.clone().try_into()?),
+        Some(_) => return Err(HandErr::InvalidSeat),
+        None if args.tsumo => None,
+        None => return Ok(None),
+    };
+    settle(score, &winner, loser.as_ref(), &ruleset(args)).map(Some)
+}
+
+pub fn json_calc_out(
+    payment: &Payment,
+    honba: HonbaCounter,
+    han: HanValue,
+    fu: FuValue,
+    limit: Option<LimitHands>,
+) -> String {
     let out = json!({
     "han" : han,
     "fu" : fu,
     "honba" : honba,
+    "limit" : limit.map(|limit| limit.to_string()),
         "scores" : {
             "dealer" : {
                 "ron" : payment.dealer_ron(honba),
@@ -187,41 +1415,281 @@ pub fn json_calc_out(payment: &Payment, honba: HonbaCounter, han: HanValue, fu:
     out.to_string()
 }
 
-pub fn default_calc_out(
-    payment: &Payment,
-    honba: HonbaCounter,
-    han: HanValue,
-    fu: FuValue,
-) -> String {
-    let honba_str = if honba != 0 {
-        format!("/ {honba} Honba")
-    } else {
-        "".to_string()
+pub fn default_calc_out(
+    payment: &Payment,
+    honba: HonbaCounter,
+    han: HanValue,
+    fu: FuValue,
+    limit: Option<LimitHands>,
+) -> String {
+    let honba_str = if honba != 0 {
+        format!("/ {honba} Honba")
+    } else {
+        "".to_string()
+    };
+    let limit_str = limit_to_string(limit);
+
+    format!(
+        "\n{han} Han\
+        / {fu} Fu\
+        {honba}\
+        {limit}\
+        \nDealer: {dealer_ron} ({dealer_each})\
+        \nnon-dealer: {non_dealer_ron} ({non_dealer_payment}/{dealer_payment})",
+        han = han,
+        fu = fu,
+        honba = honba_str,
+        limit = limit_str,
+        dealer_ron = payment.dealer_ron(honba),
+        dealer_each = payment.dealer_tsumo(honba),
+        non_dealer_ron = payment.non_dealer_ron(honba),
+        non_dealer_payment = payment.non_dealer_tsumo_to_non_dealer(honba),
+        dealer_payment = payment.non_dealer_tsumo_to_dealer(honba)
+    )
+}
+
+pub fn json_shanten_out(shanten: &Shanten) -> String {
+    let out = json!({
+        "shanten" : shanten.min(),
+        "standard" : shanten.standard,
+        "chiitoitsu" : shanten.chiitoitsu,
+        "kokushi" : shanten.kokushi,
+    });
+    out.to_string()
+}
+
+pub fn default_shanten_out(shanten: &Shanten) -> String {
+    let mut out = format!("\nShanten: {}", shanten_to_string(shanten.min()));
+    out.push_str(&format!(
+        "\n  Standard: {}",
+        shanten_to_string(shanten.standard)
+    ));
+    if let Some(chiitoitsu) = shanten.chiitoitsu {
+        out.push_str(&format!(
+            "\n  Chiitoitsu: {}",
+            shanten_to_string(chiitoitsu)
+        ));
+    }
+    if let Some(kokushi) = shanten.kokushi {
+        out.push_str(&format!("\n  Kokushi: {}", shanten_to_string(kokushi)));
+    }
+    out
+}
+
+fn shanten_to_string(shanten: i8) -> String {
+    match shanten {
+        -1 => "complete".to_string(),
+        0 => "tenpai".to_string(),
+        n => n.to_string(),
+    }
+}
+
+pub fn json_furiten_out(waits: &[TileGroup], furiten: &[Furiten]) -> String {
+    let out = json!({
+        "waits" : groups_to_strings(waits),
+        "furiten" : furiten
+            .iter()
+            .map(|furiten| json!({
+                "type" : furiten.furiten_type.to_string(),
+                "waits" : groups_to_strings(&furiten.waits),
+            }))
+            .collect::<Vec<serde_json::Value>>(),
+    });
+    out.to_string()
+}
+
+pub fn default_furiten_out(waits: &[TileGroup], furiten: &[Furiten]) -> String {
+    let mut out = format!("\nWaits: {}", groups_to_strings(waits).join(" "));
+    if furiten.is_empty() {
+        out.push_str("\nNot Furiten");
+        return out;
+    }
+    out.push_str("\nFuriten: ");
+    for furiten in furiten {
+        out.push_str(&format!(
+            "\n  {}: {}",
+            furiten.furiten_type,
+            groups_to_strings(&furiten.waits).join(" ")
+        ));
+    }
+    out
+}
+
+pub fn json_ukeire_out(discards: &[Discard]) -> String {
+    let out = json!({
+        "discards" : discards
+            .iter()
+            .map(|discard| json!({
+                "tile" : discard.tile.to_string(),
+                "shanten" : discard.shanten,
+                "ukeire" : discard.ukeire(),
+                "tiles" : discard
+                    .improvements
+                    .iter()
+                    .map(|improvement| json!({
+                        "tile" : improvement.tile.to_string(),
+                        "live" : improvement.live,
+                    }))
+                    .collect::<Vec<serde_json::Value>>(),
+            }))
+            .collect::<Vec<serde_json::Value>>(),
+    });
+    out.to_string()
+}
+
+pub fn default_ukeire_out(discards: &[Discard]) -> String {
+    let mut out = String::new();
+    for discard in discards {
+        out.push_str(&format!(
+            "\nDiscard {}: {} ({} tiles)",
+            discard.tile,
+            shanten_to_string(discard.shanten),
+            discard.ukeire()
+        ));
+        if !discard.improvements.is_empty() {
+            out.push_str(&format!(
+                "\n  {}",
+                discard
+                    .improvements
+                    .iter()
+                    .map(|improvement| format!("{}({})", improvement.tile, improvement.live))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ));
+        }
+    }
+    out
+}
+
+pub fn json_waits_out(waits: &[TenpaiWait]) -> String {
+    let score_value = |score: &Result<BestScore, HandErr>| match score {
+        Ok(best) => json_hand_value(&best.score),
+        Err(e) => json!({ "error" : e.to_string() }),
+    };
+    let out = json!({
+        "waits" : waits
+            .iter()
+            .map(|wait| json!({
+                "tile" : wait.tile.to_string(),
+                "yakunashi" : wait.is_yakunashi(),
+                "ron" : score_value(&wait.ron),
+                "tsumo" : score_value(&wait.tsumo),
+            }))
+            .collect::<Vec<serde_json::Value>>(),
+    });
+    out.to_string()
+}
+
+pub fn default_waits_out(waits: &[TenpaiWait]) -> String {
+    let mut out = format!(
+        "\nWaits: {}",
+        waits
+            .iter()
+            .map(|wait| wait.tile.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    );
+    for wait in waits {
+        out.push_str(&format!("\n{}", wait.tile));
+        if wait.is_yakunashi() {
+            out.push_str(" (yakunashi)");
+        }
+        for (method, score) in [("Ron", &wait.ron), ("Tsumo", &wait.tsumo)] {
+            match score {
+                Ok(best) => out.push_str(&format!(
+                    "\n  {}: {}\n    Dealer: {}\n    Non-dealer: {}",
+                    method,
+                    han_fu_to_string(&best.score),
+                    payment_to_string(&best.score, true, method == "Tsumo"),
+                    payment_to_string(&best.score, false, method == "Tsumo"),
+                )),
+                Err(e) => out.push_str(&format!("\n  {}: {}", method, e)),
+            }
+        }
+    }
+    out
+}
+
+fn han_fu_to_string(score: &Score) -> String {
+    match score.limit_hand() {
+        Some(limit @ LimitHands::Yakuman(_)) => limit.to_string(),
+        limit => format!(
+            "{} Han/ {} Fu{}",
+            score.han(),
+            score.fu_score(),
+            limit_to_string(limit)
+        ),
+    }
+}
+
+fn limit_to_string(limit: Option<LimitHands>) -> String {
+    match limit {
+        Some(limit) => format!(" ({limit})"),
+        None => "".to_string(),
+    }
+}
+
+fn payment_to_string(score: &Score, dealer: bool, tsumo: bool) -> String {
+    let payment = score.payment();
+    let honba = score.honba();
+    match (dealer, tsumo) {
+        (true, false) => payment.dealer_ron(honba).to_string(),
+        (true, true) => format!("{} all", payment.dealer_tsumo(honba)),
+        (false, false) => payment.non_dealer_ron(honba).to_string(),
+        (false, true) => format!(
+            "{}/{}",
+            payment.non_dealer_tsumo_to_non_dealer(honba),
+            payment.non_dealer_tsumo_to_dealer(honba)
+        ),
+    }
+}
+
+pub fn json_hand_out(score: &Score, settlement: Option<&Settlement>) -> String {
+    let mut out = json_hand_value(score);
+    if let Some(settlement) = settlement {
+        out["settlement"] = json_settlement_value(settlement);
+    }
+    out.to_string()
+}
+
+fn json_settlement_value(settlement: &Settlement) -> serde_json::Value {
+    SEATS
+        .iter()
+        .zip(&settlement.deltas)
+        .map(|(seat, delta)| (seat.to_string(), json!(delta)))
+        .collect::<serde_json::Map<String, serde_json::Value>>()
+        .into()
+}
+
+fn settlement_to_string(settlement: Option<&Settlement>) -> String {
+    let Some(settlement) = settlement else {
+        return "".to_string();
     };
-
-    format!(
-        "\n{han} Han\
-        / {fu} Fu\
-        {honba}\
-        \nDealer: {dealer_ron} ({dealer_each})\
-        \nnon-dealer: {non_dealer_ron} ({non_dealer_payment}/{dealer_payment})",
-        han = han,
-        fu = fu,
-        honba = honba_str,
-        dealer_ron = payment.dealer_ron(honba),
-        dealer_each = payment.dealer_tsumo(honba),
-        non_dealer_ron = payment.non_dealer_ron(honba),
-        non_dealer_payment = payment.non_dealer_tsumo_to_non_dealer(honba),
-        dealer_payment = payment.non_dealer_tsumo_to_dealer(honba)
-    )
+    let mut out = "\nSettlement: ".to_string();
+    for (seat, delta) in SEATS.iter().zip(&settlement.deltas) {
+        match delta {
+            0 => out.push_str(&format!("\n  {seat}: 0")),
+            delta => out.push_str(&format!("\n  {seat}: {delta:+}")),
+        }
+    }
+    out
 }
 
-pub fn json_hand_out(score: &Score) -> String {
-    let out = json!({
+pub fn json_hand_value(score: &Score) -> serde_json::Value {
+    json!({
         "han" : score.han(),
         "fu" : score.fu_score(),
         "honba" : score.honba(),
+        "riichiSticks" : score.riichi_sticks(),
         "dora" : score.dora_count(),
+        "doraBreakdown" : {
+            "dora" : score.dora().dora,
+            "aka" : score.dora().aka,
+            "ura" : score.dora().ura,
+            "nukidora" : score.dora().nukidora
+        },
+        "limit" : score.limit_hand().map(|limit| limit.to_string()),
+        "pao" : score.pao().map(|pao| json_pao_value(&pao.seat, score)),
         "fuString" : score.fu().iter().map(|x| x.to_string()).collect::<Vec<String>>(),
         "yakuString" : score.yaku().iter().map(|x| x.to_string(score.is_open())).collect::<Vec<String>>(),
         "scores" : {
@@ -236,13 +1704,132 @@ pub fn json_hand_out(score: &Score) -> String {
                 "non-dealer" : score.payment().non_dealer_tsumo_to_non_dealer(score.honba())
                 }
             }
+        },
+        "total" : {
+            "dealer" : {
+                "ron" : score.received(true, false),
+                "tsumo" : score.received(true, true)
+            },
+            "non-dealer" : {
+                "ron" : score.received(false, false),
+                "tsumo" : score.received(false, true)
+            }
         }
-    });
+    })
+}
+
+fn json_pao_value(seat: &TileGroup, score: &Score) -> serde_json::Value {
+    let payment = score.payment();
+    let honba = score.honba();
+    let dealer_ron = payment.pao(true, false, honba);
+    let dealer_tsumo = payment.pao(true, true, honba);
+    let non_dealer_ron = payment.pao(false, false, honba);
+    let non_dealer_tsumo = payment.pao(false, true, honba);
+    json!({
+        "seat" : seat.to_string(),
+        "dealer" : {
+            "ron" : { "liable" : dealer_ron.liable, "discarder" : dealer_ron.discarder },
+            "tsumo" : { "liable" : dealer_tsumo.liable, "non-dealer" : dealer_tsumo.non_dealer }
+        },
+        "non-dealer" : {
+            "ron" : { "liable" : non_dealer_ron.liable, "discarder" : non_dealer_ron.discarder },
+            "tsumo" : {
+                "liable" : non_dealer_tsumo.liable,
+                "dealer" : non_dealer_tsumo.dealer,
+                "non-dealer" : non_dealer_tsumo.non_dealer
+            }
+        }
+    })
+}
+
+/// Payments with a liable player: the ron split between the liable player and the discarder, then what the liable player pays on a tsumo and the tsumo of the rest of the hand.
+fn pao_to_string(score: &Score, dealer: bool) -> String {
+    let ron = score.payment().pao(dealer, false, score.honba());
+    let tsumo = score.payment().pao(dealer, true, score.honba());
+    let rest = match (dealer, tsumo.non_dealer) {
+        (_, 0) => "".to_string(),
+        (true, non_dealer) => format!(" + {non_dealer} all"),
+        (false, non_dealer) => format!(" + {}/{}", non_dealer, tsumo.dealer),
+    };
+    format!(
+        "{}/{} ({}{})",
+        ron.liable, ron.discarder, tsumo.liable, rest
+    )
+}
+
+pub fn json_best_out(best: &BestScore, settlement: Option<&Settlement>) -> String {
+    let mut out = json_hand_value(&best.score);
+    if let Some(settlement) = settlement {
+        out["settlement"] = json_settlement_value(settlement);
+    }
+    out["groups"] = json!(groups_to_strings(best.hand.groups()));
+    out["wait"] = json!(wait_to_string(&best.hand));
+    out["rejected"] = best
+        .rejected
+        .iter()
+        .map(|alternative| match &alternative.score {
+            Ok(score) => json!({
+                "groups" : groups_to_strings(alternative.hand.groups()),
+                "wait" : wait_to_string(&alternative.hand),
+                "han" : score.han(),
+                "fu" : score.fu_score(),
+                "yakuString" : score.yaku().iter().map(|x| x.to_string(score.is_open())).collect::<Vec<String>>(),
+            }),
+            Err(e) => json!({
+                "groups" : groups_to_strings(alternative.hand.groups()),
+                "wait" : wait_to_string(&alternative.hand),
+                "error" : e.to_string(),
+            }),
+        })
+        .collect();
     out.to_string()
 }
+
+pub fn default_best_out(best: &BestScore) -> String {
+    let mut out = default_hand_out(&best.score);
+    out.push_str(&format!(
+        "\nGroups: {}\nWait: {}",
+        groups_to_strings(best.hand.groups()).join(" "),
+        wait_to_string(&best.hand)
+    ));
+
+    if !best.rejected.is_empty() {
+        out.push_str("\nRejected: ");
+        for alternative in &best.rejected {
+            let groups = format!(
+                "{} ({})",
+                groups_to_strings(alternative.hand.groups()).join(" "),
+                wait_to_string(&alternative.hand)
+            );
+            match &alternative.score {
+                Ok(score) => out.push_str(&format!(
+                    "\n  {}: {} Han/ {} Fu",
+                    groups,
+                    score.han(),
+                    score.fu_score()
+                )),
+                Err(e) => out.push_str(&format!("\n  {}: {}", groups, e)),
+            }
+        }
+    }
+
+    out
+}
+
+fn groups_to_strings(groups: &[TileGroup]) -> Vec<String> {
+    groups.iter().map(|group| group.to_string()).collect()
+}
+
+fn wait_to_string(hand: &Hand) -> String {
+    let wait = hand.wait();
+    format!("{} {}", wait.wait_type, hand.groups()[wait.group])
+}
 pub fn default_hand_out(score: &Score) -> String {
     let mut out: String = String::new();
-    if !score.yaku()[0].is_yakuman() {
+    let yakuman = matches!(score.limit_hand(), Some(LimitHands::Yakuman(_)));
+    if let Some(limit @ LimitHands::Yakuman(_)) = score.limit_hand() {
+        out.push_str(&format!("\n{limit}"));
+    } else {
         if score.honba() != 0 {
             out.push_str(
                 format!(
@@ -256,6 +1843,7 @@ pub fn default_hand_out(score: &Score) -> String {
         } else {
             out.push_str(&format!("\n{} Han/ {} Fu", score.han(), score.fu_score()))
         }
+        out.push_str(&limit_to_string(score.limit_hand()));
     }
 
     out.push_str(
@@ -272,8 +1860,38 @@ pub fn default_hand_out(score: &Score) -> String {
         .as_str(),
     );
 
-    if !score.yaku()[0].is_yakuman() && score.dora_count() != 0 {
-        out.push_str(format!("\nDora: {}", score.dora_count()).as_str());
+    if score.riichi_sticks() != 0 {
+        out.push_str(&format!(
+            "\nRiichi Sticks: {}\nTotal: \n  Dealer: {} ({})\n  Non-dealer: {} ({})",
+            score.riichi_sticks(),
+            score.received(true, false),
+            score.received(true, true),
+            score.received(false, false),
+            score.received(false, true)
+        ));
+    }
+
+    if let Some(pao) = score.pao() {
+        out.push_str(&format!(
+            "\nPao: {}\n  Dealer: {}\n  Non-dealer: {}",
+            pao.seat,
+            pao_to_string(score, true),
+            pao_to_string(score, false)
+        ));
+    }
+
+    if !yakuman {
+        let dora = score.dora();
+        for (name, count) in [
+            ("Dora", dora.dora),
+            ("Aka", dora.aka),
+            ("Ura", dora.ura),
+            ("Nukidora", dora.nukidora),
+        ] {
+            if count != 0 {
+                out.push_str(format!("\n{name}: {count}").as_str());
+            }
+        }
     }
 
     out.push_str("\nYaku: ");
@@ -281,7 +1899,7 @@ pub fn default_hand_out(score: &Score) -> String {
         out.push_str(format!("\n  {}", yaku.to_string(score.is_open())).as_str());
     }
 
-    if !score.yaku()[0].is_yakuman() {
+    if !yakuman {
         out.push_str("\nFu: ");
         for fu in score.fu() {
             out.push_str(format!("\n  {}", fu).as_str());
@@ -308,13 +1926,11 @@ pub fn parse_file(args: &Args) {
                 current_line_args.push(arg.into());
             }
             let args = Args::parse_from(&current_line_args);
-            if let Some(_) = args.file {
+            if args.file.is_some() {
                 parse_file(&args);
                 Ok("".to_string())
-            } else if args.manual.is_some() {
-                parse_calculator(&args)
             } else {
-                parse_hand(&args)
+                parse_args(&args)
             }
         })
         .collect();
@@ -325,6 +1941,31 @@ pub fn parse_file(args: &Args) {
     }
 }
 
+/// Run the mode selected by the arguments.
+pub fn parse_args(args: &Args) -> Result<String, HandErr> {
+    if let Some(command) = &args.command {
+        match command {
+            Command::Shanten { .. } => parse_shanten(args),
+            Command::Furiten { .. } => parse_furiten(args),
+            Command::Ukeire { .. } => parse_ukeire(args),
+            Command::Waits { .. } => parse_waits(args),
+            Command::Draw { .. } => parse_draw(args),
+            Command::Nagashi { .. } => parse_nagashi(args),
+            Command::Game { .. } => parse_game(args),
+            Command::Standings { .. } => parse_standings(args),
+            Command::Orasu { .. } => parse_orasu(args),
+            Command::Lookup { .. } => parse_lookup(args),
+            Command::Table { .. } => parse_table(args),
+        }
+    } else if args.manual.is_some() {
+        parse_calculator(args)
+    } else if args.decompose {
+        parse_decompositions(args)
+    } else {
+        parse_hand(args)
+    }
+}
+
 pub fn printout(result: &Result<String, HandErr>) {
     match result {
         Ok(o) => {
@@ -347,7 +1988,7 @@ pub fn writeout(result: &Result<String, HandErr>, output: &str) {
         Ok(o) => o.clone(),
         Err(e) => e.to_string(),
     };
-    content.push_str("\n");
+    content.push('\n');
 
     file.write_all(content.as_bytes())
         .expect("unable to write to file");
@@ -356,14 +1997,11 @@ pub fn writeout(result: &Result<String, HandErr>, output: &str) {
 fn main() {
     let args = Args::parse();
 
-    let result = if let Some(_) = args.file {
+    if args.file.is_some() {
         parse_file(&args);
         return;
-    } else if args.manual.is_some() {
-        parse_calculator(&args)
-    } else {
-        parse_hand(&args)
-    };
+    }
+    let result = parse_args(&args);
 
     if let Some(output) = &args.output {
         writeout(&result, output);
@@ -426,7 +2064,7 @@ mod test {
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n3 Han/ 70 Fu/ 3 Honba\nDealer: 12900 (4300)\nnon-dealer: 8900 (2300/4300)"
+            ("\n3 Han/ 70 Fu/ 3 Honba (Mangan)\nDealer: 12900 (4300)\nnon-dealer: 8900 (2300/4300)"
                 .to_string())
         );
     }
@@ -436,7 +2074,7 @@ mod test {
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n4 Han/ 60 Fu/ 3 Honba\nDealer: 12900 (4300)\nnon-dealer: 8900 (2300/4300)"
+            ("\n4 Han/ 60 Fu/ 3 Honba (Mangan)\nDealer: 12900 (4300)\nnon-dealer: 8900 (2300/4300)"
                 .to_string())
         );
     }
@@ -446,7 +2084,7 @@ mod test {
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n5 Han/ 70 Fu/ 3 Honba\nDealer: 12900 (4300)\nnon-dealer: 8900 (2300/4300)"
+            ("\n5 Han/ 70 Fu/ 3 Honba (Mangan)\nDealer: 12900 (4300)\nnon-dealer: 8900 (2300/4300)"
                 .to_string())
         );
     }
@@ -456,14 +2094,14 @@ mod test {
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n6 Han/ 70 Fu/ 3 Honba\nDealer: 18900 (6300)\nnon-dealer: 12900 (3300/6300)"
+            ("\n6 Han/ 70 Fu/ 3 Honba (Haneman)\nDealer: 18900 (6300)\nnon-dealer: 12900 (3300/6300)"
                 .to_string())
         );
         let args = Args::parse_from(["", "--manual", "7", "70", "--ba", "3"]);
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n7 Han/ 70 Fu/ 3 Honba\nDealer: 18900 (6300)\nnon-dealer: 12900 (3300/6300)"
+            ("\n7 Han/ 70 Fu/ 3 Honba (Haneman)\nDealer: 18900 (6300)\nnon-dealer: 12900 (3300/6300)"
                 .to_string())
         );
     }
@@ -473,21 +2111,21 @@ mod test {
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n8 Han/ 70 Fu/ 3 Honba\nDealer: 24900 (8300)\nnon-dealer: 16900 (4300/8300)"
+            ("\n8 Han/ 70 Fu/ 3 Honba (Baiman)\nDealer: 24900 (8300)\nnon-dealer: 16900 (4300/8300)"
                 .to_string())
         );
         let args = Args::parse_from(["", "--manual", "9", "70", "--ba", "3"]);
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n9 Han/ 70 Fu/ 3 Honba\nDealer: 24900 (8300)\nnon-dealer: 16900 (4300/8300)"
+            ("\n9 Han/ 70 Fu/ 3 Honba (Baiman)\nDealer: 24900 (8300)\nnon-dealer: 16900 (4300/8300)"
                 .to_string())
         );
         let args = Args::parse_from(["", "--manual", "10", "70", "--ba", "3"]);
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n10 Han/ 70 Fu/ 3 Honba\nDealer: 24900 (8300)\nnon-dealer: 16900 (4300/8300)"
+            ("\n10 Han/ 70 Fu/ 3 Honba (Baiman)\nDealer: 24900 (8300)\nnon-dealer: 16900 (4300/8300)"
                 .to_string())
         );
     }
@@ -497,25 +2135,881 @@ mod test {
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n11 Han/ 70 Fu/ 3 Honba\nDealer: 36900 (12300)\nnon-dealer: 24900 (6300/12300)"
+            ("\n11 Han/ 70 Fu/ 3 Honba (Sanbaiman)\nDealer: 36900 (12300)\nnon-dealer: 24900 (6300/12300)"
                 .to_string())
         );
         let args = Args::parse_from(["", "--manual", "12", "70", "--ba", "3"]);
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n12 Han/ 70 Fu/ 3 Honba\nDealer: 36900 (12300)\nnon-dealer: 24900 (6300/12300)"
+            ("\n12 Han/ 70 Fu/ 3 Honba (Sanbaiman)\nDealer: 36900 (12300)\nnon-dealer: 24900 (6300/12300)"
                 .to_string())
         );
     }
+    #[test]
+    fn decompositions_out() {
+        let args = Args::parse_from(["", "--hand", "222333444m5599p", "-w", "9p", "--decompose"]);
+        let out = parse_decompositions(&args);
+        assert_eq!(
+            out.unwrap(),
+            "\n222m 333m 444m 55p 999p\n234m 234m 234m 55p 999p".to_string()
+        );
+
+        let args = Args::parse_from([
+            "",
+            "--hand",
+            "123m456s99p",
+            "--calls",
+            "rrrdo",
+            "555so",
+            "-w",
+            "9p",
+            "--decompose",
+            "--json",
+        ]);
+        let out = parse_decompositions(&args);
+        assert_eq!(
+            out.unwrap(),
+            r#"{"decompositions":[["rrrdo","555so","123m","99p","456s"]]}"#.to_string()
+        );
+    }
+
+    #[test]
+    fn ungrouped_hand_score() {
+        let args = Args::parse_from([
+            "",
+            "--hand",
+            "777z111z234p234p1p",
+            "-w",
+            "1p",
+            "-p",
+            "Ew",
+            "-s",
+            "Ew",
+        ]);
+        let grouped = Args::parse_from([
+            "", "--tiles", "777z", "111z", "234p", "234p", "11p", "-w", "1p", "-p", "Ew", "-s",
+            "Ew",
+        ]);
+        let out = parse_hand(&args).unwrap();
+        assert!(out.starts_with(&parse_hand(&grouped).unwrap()));
+        assert!(out.ends_with(
+            "\nGroups: 11p 234p 234p EEEw rrrd\nWait: Tanki 11p\nRejected: \n  123p 123p 44p EEEw rrrd (Ryanmen 123p): 7 Han/ 50 Fu"
+        ));
+
+        let args = Args::parse_from(["", "--hand", "777z111z234p234p1p", "-w", "2p"]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::InvalidShape);
+    }
+
     #[test]
     fn kazoeyakuman_calc() {
         let args = Args::parse_from(["", "--manual", "13", "70", "--ba", "3"]);
         let out = parse_calculator(&args);
         assert_eq!(
             out.unwrap(),
-            ("\n13 Han/ 70 Fu/ 3 Honba\nDealer: 48900 (16300)\nnon-dealer: 32900 (8300/16300)"
+            ("\n13 Han/ 70 Fu/ 3 Honba (Kazoe Yakuman)\nDealer: 48900 (16300)\nnon-dealer: 32900 (8300/16300)"
                 .to_string())
         );
     }
+
+    #[test]
+    fn shanten_out() {
+        let args = Args::parse_from(["", "shanten", "123m456p789s1123z"]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\nShanten: 1\n  Standard: 1\n  Chiitoitsu: 5\n  Kokushi: 7"
+        );
+
+        let args = Args::parse_from([
+            "", "shanten", "2345m", "--calls", "rrrdo", "111po", "789so", "--json",
+        ]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            json!({"shanten": 0, "standard": 0, "chiitoitsu": null, "kokushi": null}).to_string()
+        );
+
+        let args = Args::parse_from(["", "shanten", "123m"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::InvalidShape);
+    }
+
+    #[test]
+    fn waits_out() {
+        let args = Args::parse_from(["", "waits", "234m456p789s1144z", "-s", "Sw"]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\nWaits: Ew Nw\nEw\n  Ron: 1 Han/ 40 Fu\n    Dealer: 2000\n    Non-dealer: 1300\n  Tsumo: 2 Han/ 30 Fu\n    Dealer: 1000 all\n    Non-dealer: 500/1000\nNw (yakunashi)\n  Ron: No Yaku\n  Tsumo: 1 Han/ 40 Fu\n    Dealer: 700 all\n    Non-dealer: 400/700"
+        );
+
+        let args = Args::parse_from(["", "waits", "234m456p789s1144z", "-r", "--json"]);
+        let out: serde_json::Value = serde_json::from_str(&parse_args(&args).unwrap()).unwrap();
+        assert_eq!(out["waits"][1]["tile"], "Nw");
+        assert_eq!(out["waits"][1]["yakunashi"], false);
+        assert_eq!(out["waits"][1]["ron"]["han"], 1);
+
+        let args = Args::parse_from(["", "waits", "2468m456p789s112z"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::NotTenpai);
+    }
+
+    #[test]
+    fn ukeire_out() {
+        let args = Args::parse_from([
+            "",
+            "ukeire",
+            "123m456p789s111z34z",
+            "-d",
+            "Ww",
+            "--visible",
+            "444z",
+        ]);
+        assert!(parse_args(&args).unwrap().starts_with(
+            "\nDiscard Nw: tenpai (2 tiles)\n  Ww(2)\nDiscard Ww: tenpai (0 tiles)\n  Nw(0)\nDiscard 1m: 1 (6 tiles)\n  4m(4) Ww(2) Nw(0)"
+        ));
+
+        let args = Args::parse_from(["", "ukeire", "123m456p789s111z34z", "--json"]);
+        let out: serde_json::Value = serde_json::from_str(&parse_args(&args).unwrap()).unwrap();
+        assert_eq!(out["discards"][0]["tile"], "Ww");
+        assert_eq!(out["discards"][0]["shanten"], 0);
+        assert_eq!(out["discards"][0]["tiles"][0]["live"], 3);
+    }
+
+    #[test]
+    fn furiten_out() {
+        let args = Args::parse_from([
+            "",
+            "furiten",
+            "2345678m456p789s",
+            "--discards",
+            "8m9p",
+            "--passed",
+            "2m",
+            "-r",
+        ]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\nWaits: 2m 5m 8m\nFuriten: \n  Permanent: 8m\n  Riichi: 2m"
+        );
+
+        let args = Args::parse_from([
+            "",
+            "furiten",
+            "2345678m456p789s",
+            "--discards",
+            "1m",
+            "--json",
+        ]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            json!({"waits": ["2m", "5m", "8m"], "furiten": []}).to_string()
+        );
+    }
+
+    #[test]
+    fn furiten_ron_rejected() {
+        let args = Args::parse_from([
+            "",
+            "--hand",
+            "2345678m456p789s",
+            "-w",
+            "5m",
+            "-r",
+            "--discards",
+            "8m",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::FuritenRon);
+
+        let args = Args::parse_from([
+            "", "--tiles", "234m", "567m", "88m", "456p", "789s", "-w", "8m", "-r", "--passed",
+            "2m",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::FuritenRon);
+
+        // tsumo is allowed while furiten
+        let args = Args::parse_from([
+            "",
+            "--hand",
+            "2345678m456p789s",
+            "-w",
+            "5m",
+            "-r",
+            "-t",
+            "--discards",
+            "8m",
+        ]);
+        assert!(parse_hand(&args).is_ok());
+    }
+
+    #[test]
+    fn situational_rules() {
+        let args = Args::parse_from([
+            "", "--tiles", "123mo", "456p", "789s", "EEEw", "11s", "-w", "1s", "-r",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::RiichiOpenHand);
+
+        let args = Args::parse_from([
+            "", "--tiles", "123m", "456p", "789s", "EEEw", "11s", "-w", "1s", "--ura", "9s",
+        ]);
+        assert_eq!(
+            parse_hand(&args).unwrap_err(),
+            HandErr::UraDoraWithoutRiichi
+        );
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "123m",
+            "456p",
+            "789s",
+            "EEEEw",
+            "11s",
+            "-w",
+            "1s",
+            "-t",
+            "--rinshan",
+            "--haitei",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::HaiteiRinshan);
+
+        let args = Args::parse_from([
+            "", "--tiles", "123m", "456p", "789s", "EEEEw", "11s", "-w", "1s", "-t", "--tenhou",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::TenhouWithCalls);
+
+        let args = Args::parse_from([
+            "", "--tiles", "123m", "456p", "789s", "234m", "55p", "-w", "5p", "--tenhou",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::TenhouWithoutTsumo);
+    }
+
+    #[test]
+    fn ura_dora_counted() {
+        let args = Args::parse_from([
+            "", "--tiles", "123m", "456p", "789s", "EEEw", "11s", "-w", "1s", "-r", "--ura", "9s",
+            "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        assert_eq!(out["dora"], 2);
+        assert_eq!(out["doraBreakdown"]["dora"], 0);
+        assert_eq!(out["doraBreakdown"]["ura"], 2);
+        assert_eq!(out["han"], 5);
+
+        // red fives only count once
+        let args = Args::parse_from([
+            "", "--tiles", "123m", "406p", "789s", "EEEw", "11s", "-w", "1s", "-r", "--ura", "9s",
+            "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        assert_eq!(out["dora"], 3);
+        assert_eq!(out["doraBreakdown"]["aka"], 1);
+        assert_eq!(out["doraBreakdown"]["ura"], 2);
+
+        let args = Args::parse_from([
+            "", "--tiles", "123m", "406p", "789s", "EEEw", "11s", "-w", "1s", "-r", "--ura", "9s",
+            "-d", "8s",
+        ]);
+        assert!(parse_hand(&args)
+            .unwrap()
+            .contains("\nDora: 1\nAka: 1\nUra: 2\nYaku:"));
+    }
+
+    #[test]
+    fn houtei_out() {
+        let args = Args::parse_from([
+            "", "--tiles", "123m", "456p", "789s", "WWWw", "11s", "-w", "1s", "--houtei", "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        assert_eq!(out["yakuString"], json!(["Houtei: 1"]));
+
+        let args = Args::parse_from([
+            "", "--tiles", "123m", "456p", "789s", "WWWw", "11s", "-w", "1s", "--haitei", "-t",
+            "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        assert_eq!(out["yakuString"], json!(["Haitei: 1", "MenzenTsumo: 1"]));
+    }
+
+    #[test]
+    fn rules_preset() {
+        let args = Args::parse_from(["", "-m", "4", "30", "--json"]);
+        let out: serde_json::Value = serde_json::from_str(&parse_args(&args).unwrap()).unwrap();
+        assert_eq!(out["scores"]["non-dealer"]["ron"], 7_700);
+
+        let args = Args::parse_from(["", "-m", "4", "30", "--rules", "wrc", "--json"]);
+        let out: serde_json::Value = serde_json::from_str(&parse_args(&args).unwrap()).unwrap();
+        assert_eq!(out["scores"]["non-dealer"]["ron"], 8_000);
+
+        let args = Args::parse_from([
+            "", "--tiles", "234mo", "456p", "678s", "222s", "55m", "-w", "5m", "--rules", "ema",
+        ]);
+        assert!(parse_hand(&args).is_ok());
+
+        assert!(Args::try_parse_from(["", "-m", "4", "30", "--rules", "mleague"]).is_err());
+    }
+
+    #[test]
+    fn limit_hand_out() {
+        let args = Args::parse_from([
+            "", "--tiles", "123p", "456p", "789p", "rrrdo", "99p", "-w", "9p", "-d", "9p", "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        assert_eq!(out["limit"], "Mangan");
+
+        let args = Args::parse_from(["", "-m", "2", "30", "--json"]);
+        let out: serde_json::Value = serde_json::from_str(&parse_args(&args).unwrap()).unwrap();
+        assert_eq!(out["limit"], serde_json::Value::Null);
+
+        let args = Args::parse_from([
+            "", "--tiles", "1p", "9p", "1s", "9s", "1m", "9m", "rd", "gd", "wd", "Ew", "Sw", "Nw",
+            "WWw", "-w", "Ww",
+        ]);
+        assert!(parse_hand(&args).unwrap().starts_with("\nDouble Yakuman\n"));
+
+        let args = Args::parse_from([
+            "", "--tiles", "1p", "9p", "1s", "9s", "1m", "9m", "rd", "gd", "wd", "Ew", "Sw", "Nw",
+            "WWw", "-w", "Ww", "--rules", "tenhou", "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        assert_eq!(out["limit"], "Yakuman");
+    }
+
+    #[test]
+    fn aotenjou_out() {
+        let args = Args::parse_from(["", "-m", "13", "30", "--aotenjou", "--json"]);
+        let out: serde_json::Value = serde_json::from_str(&parse_args(&args).unwrap()).unwrap();
+        assert_eq!(out["limit"], serde_json::Value::Null);
+        assert_eq!(out["scores"]["non-dealer"]["ron"], 3_932_200);
+
+        let args = Args::parse_from(["", "-m", "60", "30", "--aotenjou"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::ScoreOverflow);
+
+        let args = Args::parse_from([
+            "",
+            "-m",
+            "53",
+            "30",
+            "--aotenjou",
+            "--ba",
+            "40000000000000000",
+        ]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::ScoreOverflow);
+        let args = Args::parse_from(["", "-m", "2", "30", "--ba", "100000000000000000"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::ScoreOverflow);
+        let args = Args::parse_from(["", "table", "--ba", "100000000000000000"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::ScoreOverflow);
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "1p",
+            "9p",
+            "1s",
+            "9s",
+            "1m",
+            "9m",
+            "rd",
+            "gd",
+            "wd",
+            "Ew",
+            "Sw",
+            "Nw",
+            "WWw",
+            "-w",
+            "Ww",
+            "--aotenjou",
+        ]);
+        let out = parse_hand(&args).unwrap();
+        assert!(out.starts_with("\n26 Han/ "));
+        assert!(out.contains("\nFu: "));
+    }
+
+    #[test]
+    fn sanma_out() {
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "111m",
+            "456p",
+            "789s",
+            "WWWw",
+            "99m",
+            "-w",
+            "9m",
+            "-t",
+            "-s",
+            "Sw",
+            "-d",
+            "1m",
+            "--nukidora",
+            "1",
+            "--rules",
+            "majsoul-sanma",
+            "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        // 9m pair from the 1m indicator and one nukidora
+        assert_eq!(out["dora"], 3);
+        assert_eq!(out["doraBreakdown"]["dora"], 2);
+        assert_eq!(out["doraBreakdown"]["nukidora"], 1);
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "234m",
+            "456p",
+            "789s",
+            "WWWw",
+            "99m",
+            "-w",
+            "9m",
+            "--rules",
+            "tenhou-sanma",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::InvalidSanmaTile);
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "111m",
+            "456p",
+            "789s",
+            "WWWw",
+            "99m",
+            "-w",
+            "9m",
+            "--nukidora",
+            "1",
+        ]);
+        assert_eq!(
+            parse_hand(&args).unwrap_err(),
+            HandErr::NukidoraWithoutSanma
+        );
+    }
+
+    #[test]
+    fn pao_out() {
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "wwwdo",
+            "gggdo",
+            "rrrdo",
+            "EEEw",
+            "SSw",
+            "-w",
+            "Sw",
+            "-s",
+            "Sw",
+            "--called-from",
+            "wwwdo:Ew",
+            "gggdo:Ww",
+            "rrrdo:Nw",
+        ]);
+        let out = parse_hand(&args).unwrap();
+        assert!(out.contains("\nPao: Nw\n  Dealer: 24000/72000 (48000 + 16000 all)\n  Non-dealer: 16000/48000 (32000 + 8000/16000)"));
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "wwwdo",
+            "gggdo",
+            "rrrdo",
+            "EEEw",
+            "99p",
+            "-w",
+            "9p",
+            "-s",
+            "Sw",
+            "--called-from",
+            "wwwdo:Ew",
+            "gggdo:Ww",
+            "rrrdo:Nw",
+            "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        assert_eq!(out["pao"]["seat"], "Nw");
+        assert_eq!(out["pao"]["non-dealer"]["ron"]["liable"], 16_000);
+        assert_eq!(out["pao"]["non-dealer"]["ron"]["discarder"], 16_000);
+        assert_eq!(out["pao"]["non-dealer"]["tsumo"]["liable"], 32_000);
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "wwwdo",
+            "gggdo",
+            "rrrdo",
+            "EEEw",
+            "99p",
+            "-w",
+            "9p",
+            "--called-from",
+            "rrrdo:5m",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::InvalidGroup);
+    }
+
+    #[test]
+    fn settlement_out() {
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "123m",
+            "456p",
+            "789s",
+            "SSSw",
+            "11s",
+            "-w",
+            "1s",
+            "-s",
+            "Sw",
+            "-b",
+            "1",
+            "--discarder",
+            "Nw",
+        ]);
+        let out = parse_hand(&args).unwrap();
+        assert!(out.ends_with("\nSettlement: \n  Ew: 0\n  Sw: +1600\n  Ww: 0\n  Nw: -1600"));
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "111m",
+            "456p",
+            "789s",
+            "WWWw",
+            "99m",
+            "-w",
+            "9m",
+            "-t",
+            "-s",
+            "Sw",
+            "--rules",
+            "tenhou-sanma",
+            "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        assert_eq!(
+            out["settlement"],
+            json!({ "Ew" : -700, "Sw" : 1100, "Ww" : -400 })
+        );
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "123m",
+            "456p",
+            "789s",
+            "SSSw",
+            "11s",
+            "-w",
+            "1s",
+            "-s",
+            "Sw",
+            "--discarder",
+            "Sw",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::InvalidSeat);
+    }
+
+    #[test]
+    fn riichi_sticks_out() {
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "123m",
+            "456p",
+            "789s",
+            "SSSw",
+            "11s",
+            "-w",
+            "1s",
+            "-s",
+            "Sw",
+            "-r",
+            "--riichi-sticks",
+            "2",
+            "--discarder",
+            "Nw",
+        ]);
+        let out = parse_hand(&args).unwrap();
+        assert!(out.contains(
+            "\nRiichi Sticks: 2\nTotal: \n  Dealer: 5900 (5900)\n  Non-dealer: 4600 (4700)"
+        ));
+        assert!(out.ends_with("\n  Sw: +4600\n  Ww: 0\n  Nw: -2600"));
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "123m",
+            "456p",
+            "789s",
+            "234m",
+            "55p",
+            "-w",
+            "5p",
+            "-r",
+            "--riichi-sticks",
+            "100000000000000000",
+        ]);
+        assert_eq!(parse_hand(&args).unwrap_err(), HandErr::ScoreOverflow);
+
+        let args = Args::parse_from([
+            "",
+            "--tiles",
+            "123m",
+            "456p",
+            "789s",
+            "SSSw",
+            "11s",
+            "-w",
+            "1s",
+            "-s",
+            "Sw",
+            "--kyoutaku",
+            "1",
+            "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_hand(&args).unwrap()).unwrap();
+        assert_eq!(out["riichiSticks"], 1);
+        assert_eq!(out["total"]["non-dealer"]["ron"], 2_300);
+        assert_eq!(out["total"]["dealer"]["tsumo"], 3_100);
+    }
+
+    #[test]
+    fn game_out() {
+        let state = std::env::temp_dir().join("mahc-game-out.json");
+        let state = state.to_str().unwrap();
+        let run = |action: &[&str]| {
+            let mut args = vec!["", "game", "--state", state];
+            args.extend(action);
+            parse_args(&Args::parse_from(args))
+        };
+
+        let _ = fs::remove_file(state);
+        assert_eq!(run(&["show"]).unwrap_err(), HandErr::NoGame);
+        run(&["new", "--length", "tonpuusen"]).unwrap();
+        run(&["riichi", "Sw"]).unwrap();
+        let out = run(&[
+            "win",
+            "123m456p789s222z1s",
+            "-w",
+            "1s",
+            "-s",
+            "Sw",
+            "--discarder",
+            "Nw",
+        ])
+        .unwrap();
+        assert_eq!(
+            out,
+            "\nEast 2/ 0 Honba/ 0 Riichi Sticks\n  Player 1 (Nw): 25000\n  Player 2 (Ew): 27600 (+3600)\n  Player 3 (Sw): 25000\n  Player 4 (Ww): 22400 (-2600)"
+        );
+
+        run(&["draw", "--tenpai", "Ew"]).unwrap();
+        let out: serde_json::Value =
+            serde_json::from_str(&run(&["show", "--json"]).unwrap()).unwrap();
+        assert_eq!(out["hand"], 2);
+        assert_eq!(out["honba"], 1);
+        assert_eq!(out["players"][1]["seat"], "Ew");
+
+        run(&["draw"]).unwrap();
+        run(&["draw"]).unwrap();
+        let out = run(&["draw"]).unwrap();
+        assert!(out.ends_with("\nGame Over: Last Round"));
+        assert_eq!(run(&["abort"]).unwrap_err(), HandErr::GameOver);
+        fs::remove_file(state).unwrap();
+
+        let missing = std::env::temp_dir()
+            .join("mahc-missing-dir")
+            .join("game.json");
+        let args = Args::parse_from(["", "game", "--state", missing.to_str().unwrap(), "new"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::GameNotSaved);
+    }
+
+    #[test]
+    fn draw_out() {
+        let args = Args::parse_from(["", "draw", "--tenpai", "Ew", "Ww", "-b", "2"]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\nTenpai: Ew Ww\nNoten Payments: \n  Ew: +1500\n  Sw: -1500\n  Ww: +1500\n  Nw: -1500\nDealer: Renchan\nHonba: 3"
+        );
+
+        let args = Args::parse_from([
+            "",
+            "draw",
+            "--tenpai",
+            "Sw:99m456p11z,rrrdo,789so",
+            "--rules",
+            "tenhou-sanma",
+            "--json",
+        ]);
+        let out: serde_json::Value = serde_json::from_str(&parse_args(&args).unwrap()).unwrap();
+        assert_eq!(
+            out["payments"],
+            json!({ "Ew" : -1000, "Sw" : 2000, "Ww" : -1000 })
+        );
+        assert_eq!(out["renchan"], false);
+
+        let args = Args::parse_from(["", "draw", "--tenpai", "Sw:29m456p789s11222z"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::NotTenpai);
+    }
+
+    #[test]
+    fn nagashi_out() {
+        let args = Args::parse_from(["", "nagashi", "19m19p1234567z", "-s", "Sw", "-b", "1"]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\nNagashi Mangan (Draw)\nDealer: 4000 all\nNon-dealer: 2000/4000\nSettlement: \n  Ew: -4000\n  Sw: +8000\n  Ww: -2000\n  Nw: -2000"
+        );
+
+        let args = Args::parse_from(["", "nagashi", "19m5p", "-s", "Sw"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::NotNagashiMangan);
+        let args = Args::parse_from(["", "nagashi", "19m", "--rules", "wrc"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::NoNagashiMangan);
+    }
+
+    #[test]
+    fn standings_out() {
+        let run = |args: &[&str]| {
+            let mut full = vec!["", "standings", "18000", "42100", "31900", "8000"];
+            full.extend(args);
+            parse_args(&Args::parse_from(full)).unwrap()
+        };
+
+        assert_eq!(
+            run(&[]),
+            "\nUma: +15/+5/-5/-15/ Oka: +20.0\n  1. Player 2: 42100 (+47.1)\n  2. Player 3: 31900 (+6.9)\n  3. Player 1: 18000 (-17.0)\n  4. Player 4: 8000 (-37.0)"
+        );
+        assert_eq!(
+            run(&["--uma", "30", "10", "--return-points", "25000", "--format", "csv"]),
+            "place,player,points,score\n1,2,42100,47.1\n2,3,31900,16.9\n3,1,18000,-17.0\n4,4,8000,-47.0"
+        );
+        let json: serde_json::Value = serde_json::from_str(&run(&["--json"])).unwrap();
+        assert_eq!(json["oka"], 20_000);
+        assert_eq!(json["standings"][0]["player"], 2);
+        assert_eq!(json["standings"][0]["score"], 47.1);
+
+        let tied = Args::parse_from([
+            "",
+            "standings",
+            "30000",
+            "-2000",
+            "30000",
+            "42000",
+            "--tie-break",
+            "split",
+        ]);
+        assert_eq!(
+            parse_args(&tied).unwrap(),
+            "\nUma: +15/+5/-5/-15/ Oka: +20.0\n  1. Player 4: 42000 (+47.0)\n  2. Player 1: 30000 (+0.0)\n  2. Player 3: 30000 (+0.0)\n  4. Player 2: -2000 (-47.0)"
+        );
+        let bad_uma = Args::parse_from([
+            "",
+            "standings",
+            "30000",
+            "30000",
+            "40000",
+            "--uma",
+            "15",
+            "5",
+        ]);
+        assert_eq!(parse_args(&bad_uma).unwrap_err(), HandErr::InvalidUma);
+    }
+
+    #[test]
+    fn orasu_out() {
+        let args = Args::parse_from([
+            "", "orasu", "30000", "28000", "25000", "17000", "--player", "2",
+        ]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\nPlayer 2: 28000 (2nd)\nRon off Player 1:\n  1st: 1 Han/ 40 Fu (1300)\nRon off Player 3:\n  1st: 1 Han/ 70 Fu (2300)\nRon off Player 4:\n  1st: 1 Han/ 70 Fu (2300)\nTsumo:\n  1st: 1 Han/ 60 Fu (2000)"
+        );
+
+        let args = Args::parse_from([
+            "", "orasu", "60000", "20000", "12000", "8000", "--player", "4", "--ba", "1", "--json",
+        ]);
+        let json: serde_json::Value = serde_json::from_str(&parse_args(&args).unwrap()).unwrap();
+        assert_eq!(json["place"], 4);
+        assert_eq!(json["requirements"][0]["discarder"], 1);
+        assert_eq!(json["requirements"][0]["place"], 1);
+        assert_eq!(json["requirements"][0]["limit"], "Sanbaiman");
+
+        let args = Args::parse_from([
+            "", "orasu", "60000", "20000", "12000", "8000", "--player", "1",
+        ]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\nPlayer 1: 60000 (1st)\nAlready in 1st"
+        );
+        let args = Args::parse_from(["", "orasu", "60000", "20000", "12000", "--player", "1"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::InvalidPlayer);
+    }
+
+    #[test]
+    fn lookup_out() {
+        let args = Args::parse_from(["", "lookup", "7700"]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\n7700 (Non-dealer Ron)\n  3 Han/ 60 Fu\n  4 Han/ 30 Fu"
+        );
+
+        let args = Args::parse_from(["", "lookup", "4100all", "--dealer", "-b", "1"]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\n4100 all/ 1 Honba (Dealer Tsumo)\n  3 Han/ 70+ Fu (Mangan)\n  4 Han/ 40+ Fu (Mangan)\n  5 Han (Mangan)"
+        );
+
+        let args = Args::parse_from(["", "lookup", "8000/16000", "--json"]);
+        let json: serde_json::Value = serde_json::from_str(&parse_args(&args).unwrap()).unwrap();
+        assert_eq!(json["tsumo"], true);
+        assert_eq!(json["hands"][0]["han"], 13);
+        assert_eq!(json["hands"][1]["limit"], "Yakuman");
+
+        let args = Args::parse_from(["", "lookup", "1234"]);
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            "\n1234 (Non-dealer Ron)\n  No Han/Fu found"
+        );
+        let args = Args::parse_from(["", "lookup", "2000/3900", "--dealer"]);
+        assert_eq!(parse_args(&args).unwrap_err(), HandErr::InvalidPayment);
+    }
+
+    #[test]
+    fn table_out() {
+        let run = |args: &[&str]| {
+            let mut full = vec!["", "table", "--max-han", "1"];
+            full.extend(args);
+            parse_args(&Args::parse_from(full)).unwrap()
+        };
+
+        let text = run(&[]);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[1],
+            "Han  Fu             Dealer Ron  Dealer Tsumo  Non-dealer Ron  Non-dealer Tsumo"
+        );
+        assert_eq!(
+            lines[2],
+            "1    30             1500        500 all       1000            300/500"
+        );
+        assert_eq!(
+            lines.last().unwrap(),
+            &"13   Kazoe Yakuman  48000       16000 all     32000           8000/16000"
+        );
+
+        let md = run(&["--format", "md", "-b", "1"]);
+        assert!(md.starts_with("| Han | Fu | Dealer Ron |"));
+        assert!(md.contains("\n| 1 | 30 | 1800 | 600 all | 1300 | 400/600 |"));
+
+        let csv = run(&["--format", "csv", "--rules", "wrc"]);
+        assert!(csv.ends_with("\n13,Sanbaiman,36000,12000 all,24000,6000/12000\n13,Yakuman,48000,16000 all,32000,8000/16000"));
+
+        let html = run(&["--format", "html"]);
+        assert!(html.contains("\n    <tr><td>1</td><td>30</td><td>1500</td>"));
+        assert!(html.ends_with("</tbody>\n</table>"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&run(&["--json", "--aotenjou"])).unwrap();
+        assert_eq!(json["rows"].as_array().unwrap().len(), 9);
+        assert_eq!(json["rows"][0]["nonDealerTsumo"]["dealer"], 500);
+    }
 }
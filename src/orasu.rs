@@ -0,0 +1,268 @@
+use crate::calc::{calculate, is_possible_han_fu, rules_payment, FU_VALUES};
+use crate::hand::error::HandErr;
+use crate::limit_hand::LimitHands;
+use crate::payment::{Payment, Points, RIICHI_DEPOSIT};
+use crate::ruleset::Ruleset;
+use crate::score::{FuValue, HanValue, HonbaCounter, RiichiStickCounter};
+use crate::settlement::{add_delta, to_delta, PointDelta};
+
+/// Most han looked at for a win, a yakuman without aotenjou.
+const MAX_HAN: HanValue = 13;
+
+/// How a player wins the hand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WinMethod {
+    /// Ron off the discard of the player.
+    Ron(usize),
+    Tsumo,
+}
+
+/// The cheapest hand that is enough for a win.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimumWin {
+    pub han: HanValue,
+    pub fu: FuValue,
+    pub limit: Option<LimitHands>,
+    /// Points paid to the winner for the hand, honba included and riichi sticks not.
+    pub points: Points,
+}
+
+/// What a win needs to reach a place.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    pub method: WinMethod,
+    /// The place aimed for, from 1.
+    pub place: usize,
+    /// The cheapest win that reaches the place, `None` when no win does.
+    pub win: Option<MinimumWin>,
+}
+
+/// Get the place of a player, ties going to the player first in seat order from the first dealer.
+pub fn place(points: &[PointDelta], player: usize) -> usize {
+    1 + points
+        .iter()
+        .enumerate()
+        .filter(|(other, other_points)| {
+            **other_points > points[player] || (**other_points == points[player] && *other < player)
+        })
+        .count()
+}
+
+/// Work out the cheapest win a player needs to move up to each better place in the last hand (orasu).
+///
+/// Players are numbered in seat order from the first dealer, which breaks ties on points.
+/// Every ron off each other player is looked at, and then tsumo.
+/// The winner collects the honba and riichi sticks on the table along with the hand.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::orasu::{orasu, WinMethod};
+/// use mahc::ruleset::Ruleset;
+///
+/// let points = [30_000, 28_000, 25_000, 17_000];
+/// let needs = orasu(&points, 1, 3, 0, 0, &Ruleset::default()).unwrap();
+///
+/// // a 1 han 40 fu ron off the player in first is enough to pass them
+/// assert_eq!(needs[0].method, WinMethod::Ron(0));
+/// let win = needs[0].win.unwrap();
+/// assert_eq!((win.han, win.fu, win.points), (1, 40, 1_300));
+///
+/// // a tsumo needs 500/1000
+/// assert_eq!(needs[3].method, WinMethod::Tsumo);
+/// let win = needs[3].win.unwrap();
+/// assert_eq!((win.han, win.fu, win.points), (1, 60, 2_000));
+/// ```
+pub fn orasu(
+    points: &[PointDelta],
+    player: usize,
+    dealer: usize,
+    honba: HonbaCounter,
+    riichi_sticks: RiichiStickCounter,
+    rules: &Ruleset,
+) -> Result<Vec<Requirement>, HandErr> {
+    let players = if rules.sanma.is_some() { 3 } else { 4 };
+    if points.len() != players || player >= players || dealer >= players {
+        return Err(HandErr::InvalidPlayer);
+    }
+
+    let mut methods: Vec<WinMethod> = (0..players)
+        .filter(|other| *other != player)
+        .map(WinMethod::Ron)
+        .collect();
+    methods.push(WinMethod::Tsumo);
+
+    let sticks = riichi_sticks
+        .checked_mul(RIICHI_DEPOSIT)
+        .ok_or(HandErr::ScoreOverflow)?;
+    let sticks = to_delta(sticks)?;
+
+    let mut out = vec![];
+    for method in methods {
+        let mut wins: Vec<(usize, MinimumWin)> = vec![];
+        for (han, fu, limit, payment) in candidates(method == WinMethod::Tsumo, rules) {
+            let deltas = win_deltas(&payment, players, player, dealer, method, honba)?;
+            let mut after = points.to_vec();
+            for (points, delta) in after.iter_mut().zip(&deltas) {
+                add_delta(points, *delta)?;
+            }
+            add_delta(&mut after[player], sticks)?;
+            let win = MinimumWin {
+                han,
+                fu,
+                limit,
+                points: deltas[player] as Points,
+            };
+            wins.push((place(&after, player), win));
+        }
+        wins.sort_by_key(|(_, win)| (win.points, win.han, win.fu));
+
+        for target in 1..place(points, player) {
+            out.push(Requirement {
+                method,
+                place: target,
+                win: wins
+                    .iter()
+                    .find(|(place, _)| *place <= target)
+                    .map(|(_, win)| *win),
+            });
+        }
+    }
+
+    Ok(out)
+}
+
+/// Every han and fu a hand won this way can score, with its limit and payment under the ruleset.
+fn candidates(
+    tsumo: bool,
+    rules: &Ruleset,
+) -> Vec<(HanValue, FuValue, Option<LimitHands>, Payment)> {
+    let mut out = vec![];
+    for han in 1..=MAX_HAN {
+        for fu in FU_VALUES {
+            if !is_possible_han_fu(han, fu, tsumo) {
+                continue;
+            }
+            if let Ok(payment) = calculate(han, fu, rules) {
+                out.push((han, fu, LimitHands::get_limit_hand(han, fu, rules), payment));
+            }
+        }
+    }
+
+    // without kazoe yakuman 13 han is only a sanbaiman, so a yakuman is needed
+    if !rules.aotenjou && !rules.kazoe_yakuman {
+        let yakuman = LimitHands::Yakuman(1);
+        out.push((
+            MAX_HAN,
+            30,
+            Some(yakuman),
+            rules_payment(yakuman.get_score(), rules),
+        ));
+    }
+    out
+}
+
+/// The points every player gains or loses from a win, without riichi sticks.
+fn win_deltas(
+    payment: &Payment,
+    players: usize,
+    winner: usize,
+    dealer: usize,
+    method: WinMethod,
+    honba: HonbaCounter,
+) -> Result<Vec<PointDelta>, HandErr> {
+    let mut deltas = vec![0; players];
+    let mut pay = |payer: usize, points: Option<Points>| {
+        let points = to_delta(points.ok_or(HandErr::ScoreOverflow)?)?;
+        add_delta(&mut deltas[payer], -points)?;
+        add_delta(&mut deltas[winner], points)
+    };
+
+    match method {
+        WinMethod::Ron(discarder) if winner == dealer => {
+            pay(discarder, payment.checked_dealer_ron(honba))?
+        }
+        WinMethod::Ron(discarder) => pay(discarder, payment.checked_non_dealer_ron(honba))?,
+        WinMethod::Tsumo => {
+            for payer in (0..players).filter(|payer| *payer != winner) {
+                let points = if winner == dealer {
+                    payment.checked_dealer_tsumo(honba)
+                } else if payer == dealer {
+                    payment.checked_non_dealer_tsumo_to_dealer(honba)
+                } else {
+                    payment.checked_non_dealer_tsumo_to_non_dealer(honba)
+                };
+                pay(payer, points)?;
+            }
+        }
+    }
+    Ok(deltas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seat_order_ties() {
+        assert_eq!(place(&[25_000, 25_000, 30_000, 20_000], 0), 2);
+        assert_eq!(place(&[25_000, 25_000, 30_000, 20_000], 1), 3);
+    }
+
+    #[test]
+    fn minimum_wins() {
+        let rules = Ruleset::default();
+        let points = [40_000, 30_000, 20_000, 10_000];
+
+        // the dealer in last place, 30000 behind first
+        let needs = orasu(&points, 3, 3, 1, 1, &rules).unwrap();
+        let first = |method| {
+            needs
+                .iter()
+                .find(|need| need.method == method && need.place == 1)
+                .unwrap()
+                .win
+        };
+        // a dealer ron has to pass both first and second
+        let ron = first(WinMethod::Ron(0)).unwrap();
+        assert_eq!(ron.limit, Some(LimitHands::Baiman));
+        assert_eq!(ron.points, 24_300);
+        let tsumo = first(WinMethod::Tsumo).unwrap();
+        assert_eq!(tsumo.limit, Some(LimitHands::Baiman));
+        assert_eq!(tsumo.points, 24_300);
+        assert_eq!(needs.len(), 12);
+
+        // ties go to the player earlier in seat order
+        let needs = orasu(&[30_000, 25_000, 25_000, 20_000], 1, 3, 0, 0, &rules).unwrap();
+        let ron = needs
+            .iter()
+            .find(|need| need.method == WinMethod::Ron(0) && need.place == 1)
+            .unwrap();
+        assert_eq!(ron.win.unwrap().points, 2_600);
+
+        // no win is enough for first in sanma, tsumo loss being the cheapest
+        let needs = orasu(
+            &[80_000, 10_000, 15_000],
+            1,
+            2,
+            0,
+            0,
+            &Ruleset::tenhou_sanma(),
+        )
+        .unwrap();
+        assert!(needs
+            .iter()
+            .filter(|need| need.place == 1)
+            .all(|need| need.win.is_none()));
+        let tsumo = needs
+            .iter()
+            .find(|need| need.method == WinMethod::Tsumo && need.place == 2)
+            .unwrap();
+        assert_eq!(tsumo.win.unwrap().points, 3_000);
+
+        assert_eq!(
+            orasu(&[25_000; 3], 0, 3, 0, 0, &rules),
+            Err(HandErr::InvalidPlayer)
+        );
+    }
+}
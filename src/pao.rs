@@ -0,0 +1,179 @@
+use crate::context::CalledMeld;
+use crate::hand::Hand;
+use crate::suit::Suit;
+use crate::tile_group::{GroupType, TileGroup};
+use crate::yaku::Yaku;
+
+/// A player liable (pao) for a yakuman they fed the winner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pao {
+    /// Seat wind of the liable player.
+    pub seat: TileGroup,
+    /// The yakuman the liable player answers for.
+    pub yaku: Yaku,
+}
+
+/// Find the player liable (pao) for a daisangen, daisuushii or suukantsu in the hand.
+///
+/// A player is liable when the call that completed the last set of the yakuman was made from them.
+/// `called` holds the calls of the hand in the order they were made, concealed triplets are taken to be held before any call.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::context::CalledMeld;
+/// use mahc::hand::Hand;
+/// use mahc::pao::liable_player;
+/// use mahc::yaku::Yaku;
+///
+/// let hand = Hand::new_from_strings(
+///     vec!["wwwdo".to_string(), "gggdo".to_string(), "rrrdo".to_string(), "123m".to_string(), "99p".to_string()],
+///     "9p".to_string(),
+///     "Ew".to_string(),
+///     "Ew".to_string(),
+/// )
+/// .unwrap();
+/// let call = |meld: &str, from: &str| CalledMeld {
+///     meld: meld.to_string().try_into().unwrap(),
+///     from: Some(from.to_string().try_into().unwrap()),
+/// };
+/// let called = [call("wwwdo", "Sw"), call("gggdo", "Ww"), call("rrrdo", "Nw")];
+///
+/// let pao = liable_player(&hand, &[Yaku::Daisangen], &called).unwrap();
+/// assert_eq!(pao.seat.to_string(), "Nw");
+/// ```
+pub fn liable_player(hand: &Hand, yaku: &[Yaku], called: &[CalledMeld]) -> Option<Pao> {
+    let pao_yakuman = [
+        (
+            Yaku::Daisangen,
+            is_dragon_set as fn(&TileGroup) -> bool,
+            3usize,
+        ),
+        (Yaku::Daisuushii, is_wind_set, 4),
+        (Yaku::Suukantsu, is_kan, 4),
+    ];
+
+    for (yakuman, is_part, sets) in pao_yakuman {
+        if !yaku.contains(&yakuman) {
+            continue;
+        }
+
+        // concealed triplets count first, the set completed by the winning tile is never the one a call completed
+        let concealed = hand
+            .groups()
+            .iter()
+            .enumerate()
+            .filter(|(i, group)| {
+                is_part(group)
+                    && !group.isopen
+                    && group.group_type == GroupType::Triplet
+                    && *i != hand.wait().group
+            })
+            .count();
+        let Some(completing) = sets.checked_sub(concealed + 1) else {
+            continue;
+        };
+        let completing_call = called
+            .iter()
+            .filter(|call| is_part(&call.meld))
+            .nth(completing);
+
+        if let Some(CalledMeld {
+            from: Some(seat), ..
+        }) = completing_call
+        {
+            return Some(Pao {
+                seat: seat.clone(),
+                yaku: yakuman,
+            });
+        }
+    }
+
+    None
+}
+
+fn is_set(group: &TileGroup) -> bool {
+    group.group_type == GroupType::Triplet || group.group_type == GroupType::Kan
+}
+
+fn is_dragon_set(group: &TileGroup) -> bool {
+    is_set(group) && group.suit == Suit::Dragon
+}
+
+fn is_wind_set(group: &TileGroup) -> bool {
+    is_set(group) && group.suit == Suit::Wind
+}
+
+fn is_kan(group: &TileGroup) -> bool {
+    group.group_type == GroupType::Kan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand::test_hand;
+
+    fn call(meld: &str, from: Option<&str>) -> CalledMeld {
+        CalledMeld {
+            meld: meld.to_string().try_into().unwrap(),
+            from: from.map(|seat| seat.to_string().try_into().unwrap()),
+        }
+    }
+
+    #[test]
+    fn daisangen_liability() {
+        let open = test_hand(
+            &["wwwdo", "gggdo", "rrrdo", "123m", "99p"],
+            "9p",
+            "Ew",
+            "Ew",
+        );
+        let called = [
+            call("wwwdo", Some("Sw")),
+            call("gggdo", Some("Ww")),
+            call("rrrdo", Some("Nw")),
+        ];
+        let pao = liable_player(&open, &[Yaku::Daisangen], &called).unwrap();
+        assert_eq!(pao.seat.to_string(), "Nw");
+        assert_eq!(pao.yaku, Yaku::Daisangen);
+        assert_eq!(liable_player(&open, &[Yaku::Toitoi], &called), None);
+
+        // with a concealed triplet held, the second call completes the yakuman
+        let concealed = test_hand(&["wwwd", "gggdo", "rrrdo", "123m", "99p"], "9p", "Ew", "Ew");
+        let called = [call("gggdo", Some("Ww")), call("rrrdo", Some("Nw"))];
+        let pao = liable_player(&concealed, &[Yaku::Daisangen], &called).unwrap();
+        assert_eq!(pao.seat.to_string(), "Nw");
+
+        // the last dragon set was finished by the winning tile
+        let won = test_hand(&["wwwdo", "gggdo", "123m", "99p", "rrrd"], "rd", "Ew", "Ew");
+        let called = [call("wwwdo", Some("Sw")), call("gggdo", Some("Ww"))];
+        assert_eq!(liable_player(&won, &[Yaku::Daisangen], &called), None);
+    }
+
+    #[test]
+    fn suukantsu_liability() {
+        let kans = test_hand(
+            &["1111mo", "2222p", "3333so", "EEEEwo", "99p"],
+            "9p",
+            "Ew",
+            "Ew",
+        );
+        let called = [
+            call("1111mo", Some("Sw")),
+            call("3333so", Some("Ww")),
+            call("EEEEwo", Some("Nw")),
+            call("2222p", None),
+        ];
+        // the last kan was a closed kan
+        assert_eq!(liable_player(&kans, &[Yaku::Suukantsu], &called), None);
+
+        let called = [
+            call("2222p", None),
+            call("1111mo", Some("Sw")),
+            call("3333so", Some("Ww")),
+            call("EEEEwo", Some("Nw")),
+        ];
+        let pao = liable_player(&kans, &[Yaku::Suukantsu], &called).unwrap();
+        assert_eq!(pao.seat.to_string(), "Nw");
+    }
+}
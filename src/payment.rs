@@ -1,15 +1,22 @@
+use crate::ruleset::SanmaTsumo;
 use crate::score::{FuValue, HanValue, HonbaCounter};
 
 /// Number of points players pay to the winner.
 // NOTE: `u64` allows for scoring with aotenjou (no limits).
 pub(crate) type Points = u64;
 
+/// Points a player puts on the table to declare riichi.
+pub const RIICHI_DEPOSIT: Points = 1_000;
+
 const DEALER_RON_MULTIPLIER: u64 = 6;
 const DEALER_TSUMO_MULTIPLIER: u64 = 2;
 const NON_DEALER_RON_MULTIPLIER: u64 = 4;
 const NON_DEALER_TSUMO_TO_NON_DEALER_MULTIPLIER: u64 = 1;
 const NON_DEALER_TSUMO_TO_DEALER_MULTIPLIER: u64 = 2;
 
+/// Panic message of the payment getters when a payment doesn't fit in [`Points`].
+const TOO_LARGE: &str = "payment is too large to count";
+
 /// Methods to provide a breakdown of payment amounts players will pay.
 ///
 /// This struct stores the base points the hand was awarded.
@@ -48,6 +55,8 @@ const NON_DEALER_TSUMO_TO_DEALER_MULTIPLIER: u64 = 2;
 ///
 /// In addition to the payment, the winner is paid an additional amount of points based on the number of honba counters on the table.
 ///
+/// In a three player game (sanma) the tsumo payments and honba are split between two players, see [`Payment::with_sanma`].
+///
 /// # Examples
 ///
 /// < 5 han
@@ -88,6 +97,27 @@ pub struct Payment {
     base_points: Points,
     /// The number of points each honba (repeat counter) is worth.
     tsumibou: Points,
+    /// How a tsumo is split in a three player game, `None` for four players.
+    sanma: Option<SanmaTsumo>,
+    /// The part of the base points a liable (pao) player answers for.
+    pao_base_points: Points,
+}
+
+/// Who pays what when a player is liable (pao) for a yakuman.
+///
+/// On a tsumo the liable player pays the yakuman they answer for as if they had dealt it in, along with every honba.
+/// The rest of the hand is paid as a normal tsumo by every other player, the liable one included.
+/// On a ron the liable player and the discarder split the yakuman, the discarder pays the rest of the hand and the honba.
+#[derive(Debug, PartialEq)]
+pub struct PaoPayment {
+    /// Paid by the liable player.
+    pub liable: Points,
+    /// Paid by the discarder on a ron.
+    pub discarder: Points,
+    /// Paid by the dealer on a tsumo for the rest of the hand, nothing when the dealer won.
+    pub dealer: Points,
+    /// Paid by each non-dealer on a tsumo for the rest of the hand.
+    pub non_dealer: Points,
 }
 
 impl Payment {
@@ -96,6 +126,116 @@ impl Payment {
         Self {
             base_points,
             tsumibou: 300,
+            sanma: None,
+            pao_base_points: 0,
+        }
+    }
+
+    /// Set the number of points each honba (repeat counter) is worth, 300 by default.
+    pub fn with_tsumibou(mut self, tsumibou: Points) -> Self {
+        self.tsumibou = tsumibou;
+        self
+    }
+
+    /// Split tsumo payments between the two other players of a three player game, or four players with `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::payment::Payment;
+    /// use mahc::ruleset::SanmaTsumo;
+    ///
+    /// let mangan = Payment::new(2_000);
+    /// assert_eq!(mangan.dealer_tsumo(0), 4_000);
+    ///
+    /// let tsumo_loss = Payment::new(2_000).with_sanma(Some(SanmaTsumo::TsumoLoss));
+    /// assert_eq!(tsumo_loss.dealer_tsumo(0), 4_000);
+    ///
+    /// let bisection = Payment::new(2_000).with_sanma(Some(SanmaTsumo::NorthBisection));
+    /// assert_eq!(bisection.dealer_tsumo(0), 6_000);
+    /// assert_eq!(bisection.non_dealer_tsumo_to_dealer(0), 5_000);
+    /// assert_eq!(bisection.non_dealer_tsumo_to_non_dealer(0), 3_000);
+    /// ```
+    pub fn with_sanma(mut self, sanma: Option<SanmaTsumo>) -> Self {
+        self.sanma = sanma;
+        self
+    }
+
+    /// Set the part of the base points a liable (pao) player answers for, at most the base points.
+    pub fn with_pao(mut self, pao_base_points: Points) -> Self {
+        self.pao_base_points = pao_base_points.min(self.base_points);
+        self
+    }
+
+    /// Get the part of the base points a liable (pao) player answers for.
+    pub fn pao_base_points(&self) -> Points {
+        self.pao_base_points
+    }
+
+    /// Get the payments with a liable (pao) player.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::payment::{Payment, PaoPayment};
+    ///
+    /// let yakuman = Payment::new(8_000).with_pao(8_000);
+    /// let ron = yakuman.pao(false, false, 1);
+    /// assert_eq!(ron.liable, 16_000);
+    /// assert_eq!(ron.discarder, 16_300);
+    ///
+    /// let tsumo = yakuman.pao(true, true, 0);
+    /// assert_eq!(tsumo, PaoPayment { liable: 48_000, discarder: 0, dealer: 0, non_dealer: 0 });
+    /// ```
+    pub fn pao(&self, dealer: bool, tsumo: bool, honba: HonbaCounter) -> PaoPayment {
+        self.checked_pao(dealer, tsumo, honba).expect(TOO_LARGE)
+    }
+
+    /// Get the payments with a liable (pao) player, or `None` if a payment would overflow.
+    pub fn checked_pao(
+        &self,
+        dealer: bool,
+        tsumo: bool,
+        honba: HonbaCounter,
+    ) -> Option<PaoPayment> {
+        let multiplier = if dealer {
+            DEALER_RON_MULTIPLIER
+        } else {
+            NON_DEALER_RON_MULTIPLIER
+        };
+        let rest = Self {
+            base_points: self.base_points - self.pao_base_points,
+            tsumibou: self.tsumibou,
+            sanma: self.sanma,
+            pao_base_points: 0,
+        };
+        let honba_points = self.tsumibou.checked_mul(honba)?;
+        let liable = self.checked_round_payment(self.pao_base_points.checked_mul(multiplier)?)?;
+
+        if tsumo {
+            let (dealer_share, non_dealer_share) = if dealer {
+                (0, rest.checked_dealer_tsumo(0)?)
+            } else {
+                (
+                    rest.checked_non_dealer_tsumo_to_dealer(0)?,
+                    rest.checked_non_dealer_tsumo_to_non_dealer(0)?,
+                )
+            };
+            Some(PaoPayment {
+                liable: liable.checked_add(honba_points)?,
+                discarder: 0,
+                dealer: dealer_share,
+                non_dealer: non_dealer_share,
+            })
+        } else {
+            let half = self.round_payment(self.pao_base_points * multiplier / 2);
+            let rest = self.checked_round_payment(rest.base_points.checked_mul(multiplier)?)?;
+            Some(PaoPayment {
+                liable: half,
+                discarder: half.checked_add(rest)?.checked_add(honba_points)?,
+                dealer: 0,
+                non_dealer: 0,
+            })
         }
     }
 
@@ -124,12 +264,34 @@ impl Payment {
             fu
         } else {
             // Round up to the nearest 10.
-            (fu + 9) / 10 * 10
+            fu.div_ceil(10) * 10
         };
 
         Self::new(fu * 2u64.pow(han + 2))
     }
 
+    /// Calculate the base points with the given han and fu, or `None` if the payments would overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::payment::Payment;
+    ///
+    /// assert!(Payment::checked_from_han_and_fu(13, 30).is_some());
+    /// assert!(Payment::checked_from_han_and_fu(100, 30).is_none());
+    /// ```
+    pub fn checked_from_han_and_fu(han: HanValue, fu: FuValue) -> Option<Self> {
+        let fu = if fu == 25 { fu } else { fu.div_ceil(10) * 10 };
+        let base_points = fu.checked_mul(2u64.checked_pow(han.checked_add(2)?)?)?;
+
+        // the largest payment is a dealer ron, rounded up
+        base_points
+            .checked_mul(DEALER_RON_MULTIPLIER)?
+            .checked_add(100)?;
+
+        Some(Self::new(base_points))
+    }
+
     /// Get the base points.
     pub fn base_points(&self) -> Points {
         self.base_points
@@ -137,42 +299,159 @@ impl Payment {
 
     /// Round the payment amount to the nearest hundredth.
     fn round_payment(&self, unrounded_payment: Points) -> Points {
-        (unrounded_payment + 99) / 100 * 100
+        unrounded_payment.div_ceil(100) * 100
+    }
+
+    /// Round the payment amount to the nearest hundredth, or `None` if it would overflow.
+    fn checked_round_payment(&self, unrounded_payment: Points) -> Option<Points> {
+        unrounded_payment.div_ceil(100).checked_mul(100)
+    }
+
+    /// Check every payment of the hand, pao included, can be counted with the honba on the table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::payment::Payment;
+    ///
+    /// let mangan = Payment::new(2_000);
+    /// assert!(mangan.is_countable(3));
+    /// assert!(!mangan.is_countable(100_000_000_000_000_000));
+    /// ```
+    pub fn is_countable(&self, honba: HonbaCounter) -> bool {
+        [(true, false), (true, true), (false, false), (false, true)]
+            .into_iter()
+            .all(|(dealer, tsumo)| {
+                self.checked_total(dealer, tsumo, honba).is_some()
+                    && self.checked_pao(dealer, tsumo, honba).is_some()
+            })
     }
 
     /// Get the amount of points the player that dealt-in has to pay to a dealer.
     pub fn dealer_ron(&self, honba: HonbaCounter) -> Points {
-        self.round_payment((self.base_points * DEALER_RON_MULTIPLIER) + (self.tsumibou * honba))
+        self.checked_dealer_ron(honba).expect(TOO_LARGE)
+    }
+
+    /// Get the amount the player that dealt-in pays to a dealer, or `None` if it would overflow.
+    pub fn checked_dealer_ron(&self, honba: HonbaCounter) -> Option<Points> {
+        self.checked_ron_payment(DEALER_RON_MULTIPLIER, honba)
+    }
+
+    /// Get the total the winner is paid, honba included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::payment::Payment;
+    /// use mahc::ruleset::SanmaTsumo;
+    ///
+    /// let mangan = Payment::new(2_000);
+    /// assert_eq!(mangan.total(false, false, 1), 8_300);
+    /// assert_eq!(mangan.total(false, true, 1), 8_300);
+    ///
+    /// let tsumo_loss = mangan.with_sanma(Some(SanmaTsumo::TsumoLoss));
+    /// assert_eq!(tsumo_loss.total(false, true, 0), 6_000);
+    /// ```
+    pub fn total(&self, dealer: bool, tsumo: bool, honba: HonbaCounter) -> Points {
+        self.checked_total(dealer, tsumo, honba).expect(TOO_LARGE)
+    }
+
+    /// Get the total the winner is paid, honba included, or `None` if it would overflow.
+    pub fn checked_total(&self, dealer: bool, tsumo: bool, honba: HonbaCounter) -> Option<Points> {
+        let payers = if self.sanma.is_some() { 2 } else { 3 };
+        match (dealer, tsumo) {
+            (true, false) => self.checked_dealer_ron(honba),
+            (true, true) => self.checked_dealer_tsumo(honba)?.checked_mul(payers),
+            (false, false) => self.checked_non_dealer_ron(honba),
+            (false, true) => self.checked_non_dealer_tsumo_to_dealer(honba)?.checked_add(
+                self.checked_non_dealer_tsumo_to_non_dealer(honba)?
+                    .checked_mul(payers - 1)?,
+            ),
+        }
     }
 
     /// Get the amount of points each player pays when the dealer tsumos.
     pub fn dealer_tsumo(&self, honba: HonbaCounter) -> Points {
-        // NOTE: This is assuming a 4-player game where the tsumibou is divided by the number of other players.
-        self.round_payment(
-            (self.base_points * DEALER_TSUMO_MULTIPLIER) + ((self.tsumibou / 3) * honba),
-        )
+        self.checked_dealer_tsumo(honba).expect(TOO_LARGE)
+    }
+
+    /// Get the amount each player pays when the dealer tsumos, or `None` if it would overflow.
+    pub fn checked_dealer_tsumo(&self, honba: HonbaCounter) -> Option<Points> {
+        self.checked_tsumo_payment(DEALER_TSUMO_MULTIPLIER, DEALER_TSUMO_MULTIPLIER, honba)
     }
 
     /// Get the amount the player that dealt-in pays to a non-dealer.
     pub fn non_dealer_ron(&self, honba: HonbaCounter) -> Points {
-        self.round_payment((self.base_points * NON_DEALER_RON_MULTIPLIER) + (self.tsumibou * honba))
+        self.checked_non_dealer_ron(honba).expect(TOO_LARGE)
+    }
+
+    /// Get the amount the player that dealt-in pays to a non-dealer, or `None` if it would overflow.
+    pub fn checked_non_dealer_ron(&self, honba: HonbaCounter) -> Option<Points> {
+        self.checked_ron_payment(NON_DEALER_RON_MULTIPLIER, honba)
     }
 
     /// Get the amount the dealer pays when a non-dealer wins by tsumo.
     pub fn non_dealer_tsumo_to_dealer(&self, honba: HonbaCounter) -> Points {
-        // NOTE: This is assuming a 4-player game where the tsumibou is divided by the number of other players.
-        self.round_payment(
-            (self.base_points * NON_DEALER_TSUMO_TO_DEALER_MULTIPLIER)
-                + ((self.tsumibou / 3) * honba),
+        self.checked_non_dealer_tsumo_to_dealer(honba)
+            .expect(TOO_LARGE)
+    }
+
+    /// Get the amount the dealer pays when a non-dealer wins by tsumo, or `None` if it would overflow.
+    pub fn checked_non_dealer_tsumo_to_dealer(&self, honba: HonbaCounter) -> Option<Points> {
+        self.checked_tsumo_payment(
+            NON_DEALER_TSUMO_TO_DEALER_MULTIPLIER,
+            NON_DEALER_TSUMO_TO_NON_DEALER_MULTIPLIER,
+            honba,
         )
     }
 
     /// Get the amount non-dealer players pay when a non-dealer wins by tsumo.
     pub fn non_dealer_tsumo_to_non_dealer(&self, honba: HonbaCounter) -> Points {
-        // NOTE: This is assuming a 4-player game where the tsumibou is divided by the number of other players.
-        self.round_payment(
-            (self.base_points * NON_DEALER_TSUMO_TO_NON_DEALER_MULTIPLIER)
-                + ((self.tsumibou / 3) * honba),
+        self.checked_non_dealer_tsumo_to_non_dealer(honba)
+            .expect(TOO_LARGE)
+    }
+
+    /// Get the amount non-dealer players pay when a non-dealer wins by tsumo, or `None` if it would overflow.
+    pub fn checked_non_dealer_tsumo_to_non_dealer(&self, honba: HonbaCounter) -> Option<Points> {
+        self.checked_tsumo_payment(
+            NON_DEALER_TSUMO_TO_NON_DEALER_MULTIPLIER,
+            NON_DEALER_TSUMO_TO_NON_DEALER_MULTIPLIER,
+            honba,
+        )
+    }
+
+    /// Get the amount the discarder pays on a ron, or `None` if it would overflow.
+    fn checked_ron_payment(&self, multiplier: Points, honba: HonbaCounter) -> Option<Points> {
+        let honba_points = self.tsumibou.checked_mul(honba)?;
+        self.checked_round_payment(
+            self.base_points
+                .checked_mul(multiplier)?
+                .checked_add(honba_points)?,
+        )
+    }
+
+    /// Get the amount one player pays on a tsumo, or `None` if it would overflow.
+    ///
+    /// `missing_multiplier` is the share of the north player missing in sanma.
+    fn checked_tsumo_payment(
+        &self,
+        multiplier: Points,
+        missing_multiplier: Points,
+        honba: HonbaCounter,
+    ) -> Option<Points> {
+        let (payers, missing_share) = match self.sanma {
+            None => (3, 0),
+            Some(SanmaTsumo::TsumoLoss) => (2, 0),
+            Some(SanmaTsumo::NorthBisection) => {
+                (2, self.base_points.checked_mul(missing_multiplier)? / 2)
+            }
+        };
+        let honba_points = (self.tsumibou / payers).checked_mul(honba)?;
+        self.checked_round_payment(
+            self.base_points
+                .checked_mul(multiplier)?
+                .checked_add(missing_share)?
+                .checked_add(honba_points)?,
         )
     }
 }
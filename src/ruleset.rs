@@ -0,0 +1,269 @@
+use crate::context::WinContext;
+use crate::hand::error::HandErr;
+use crate::hand::Hand;
+use crate::payment::Points;
+use crate::tile_group::{tile_counts, TileGroup};
+
+/// Names of the rulesets that can be picked with [`Ruleset::preset`].
+pub const PRESETS: [&str; 6] = [
+    "majsoul",
+    "tenhou",
+    "wrc",
+    "ema",
+    "majsoul-sanma",
+    "tenhou-sanma",
+];
+
+/// Tile kind index of the north wind, the tile set aside as nukidora.
+const NORTH_INDEX: usize = 30;
+
+/// How the missing player's share of a tsumo is handled in sanma (three player).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SanmaTsumo {
+    /// The two other players pay what they would in a four player game, so a tsumo is worth less than a ron.
+    TsumoLoss,
+    /// The share of the missing north player is split between the two other players.
+    NorthBisection,
+}
+
+/// How nagashi mangan (a mangan from a discard pond of only terminals and honors) ends the hand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NagashiMangan {
+    /// It is a tsumo win, with the honba and riichi sticks.
+    Win,
+    /// It is paid like a mangan tsumo but the hand is an exhaustive draw, the honba and riichi sticks stay.
+    Draw,
+}
+
+/// The rules that change how a hand is scored.
+///
+/// Every scoring function takes a ruleset, [`Ruleset::default`] is the Mahjong Soul ruleset.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::ruleset::Ruleset;
+///
+/// let wrc = Ruleset::preset("wrc").unwrap();
+/// assert!(wrc.kiriage_mangan);
+/// assert_eq!(wrc.aka_dora, 0);
+///
+/// let house = Ruleset {
+///     kuitan: false,
+///     ..Ruleset::default()
+/// };
+/// assert_ne!(house, Ruleset::majsoul());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ruleset {
+    /// Tanyao counts for an open hand (kuitan).
+    pub kuitan: bool,
+    /// 4 han 30 fu and 3 han 60 fu are rounded up to mangan.
+    pub kiriage_mangan: bool,
+    /// Kokushi 13 sided wait, suuankou tanki, junsei chuuren and daisuushii count as double yakuman.
+    pub double_yakuman: bool,
+    /// 13 or more han is a kazoe yakuman, otherwise it is capped at sanbaiman.
+    pub kazoe_yakuman: bool,
+    /// Number of red fives in the tile set.
+    pub aka_dora: u8,
+    /// Points each honba (repeat counter) adds to a win.
+    pub honba_value: Points,
+    /// No limits, every score is `fu * 2 ^ (han + 2)` and each yakuman is 13 han.
+    pub aotenjou: bool,
+    /// Three player game and how its tsumo payments are split, `None` for four players.
+    pub sanma: Option<SanmaTsumo>,
+    /// Only the first player in turn order after the discarder can ron (head bump), otherwise a double ron is allowed.
+    pub atamahane: bool,
+    /// Three players calling ron on the same tile is an abortive draw (sanchahou).
+    pub triple_ron_draw: bool,
+    /// Points the noten players pay the tenpai players at an exhaustive draw (noten bappu).
+    pub noten_bappu: Points,
+    /// How nagashi mangan is paid, `None` when it is not played.
+    pub nagashi_mangan: Option<NagashiMangan>,
+}
+
+impl Ruleset {
+    /// Get a preset ruleset from its name, one of [`PRESETS`].
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "majsoul" => Some(Self::majsoul()),
+            "tenhou" => Some(Self::tenhou()),
+            "wrc" => Some(Self::wrc()),
+            "ema" => Some(Self::ema()),
+            "majsoul-sanma" => Some(Self::majsoul_sanma()),
+            "tenhou-sanma" => Some(Self::tenhou_sanma()),
+            _ => None,
+        }
+    }
+
+    /// Mahjong Soul rules.
+    pub fn majsoul() -> Self {
+        Self {
+            kuitan: true,
+            kiriage_mangan: false,
+            double_yakuman: true,
+            kazoe_yakuman: true,
+            aka_dora: 3,
+            honba_value: 300,
+            aotenjou: false,
+            sanma: None,
+            atamahane: false,
+            triple_ron_draw: true,
+            noten_bappu: 3_000,
+            nagashi_mangan: Some(NagashiMangan::Draw),
+        }
+    }
+
+    /// Tenhou rules, with no double yakuman.
+    pub fn tenhou() -> Self {
+        Self {
+            double_yakuman: false,
+            ..Self::majsoul()
+        }
+    }
+
+    /// World Riichi Championship rules.
+    pub fn wrc() -> Self {
+        Self {
+            kuitan: true,
+            kiriage_mangan: true,
+            double_yakuman: false,
+            kazoe_yakuman: false,
+            aka_dora: 0,
+            honba_value: 300,
+            aotenjou: false,
+            sanma: None,
+            atamahane: true,
+            triple_ron_draw: false,
+            noten_bappu: 3_000,
+            nagashi_mangan: None,
+        }
+    }
+
+    /// European Mahjong Association rules, which have no honba payments.
+    pub fn ema() -> Self {
+        Self {
+            kuitan: true,
+            kiriage_mangan: false,
+            double_yakuman: false,
+            kazoe_yakuman: false,
+            aka_dora: 0,
+            honba_value: 0,
+            aotenjou: false,
+            sanma: None,
+            atamahane: true,
+            triple_ron_draw: false,
+            noten_bappu: 3_000,
+            nagashi_mangan: None,
+        }
+    }
+
+    /// Mahjong Soul three player rules, with no tsumo loss.
+    pub fn majsoul_sanma() -> Self {
+        Self {
+            aka_dora: 2,
+            honba_value: 1_000,
+            noten_bappu: 2_000,
+            sanma: Some(SanmaTsumo::NorthBisection),
+            ..Self::majsoul()
+        }
+    }
+
+    /// Tenhou three player rules, with tsumo loss.
+    pub fn tenhou_sanma() -> Self {
+        Self {
+            aka_dora: 2,
+            honba_value: 200,
+            noten_bappu: 2_000,
+            sanma: Some(SanmaTsumo::TsumoLoss),
+            ..Self::tenhou()
+        }
+    }
+
+    /// Check the hand and its context can happen with the tiles of this ruleset.
+    ///
+    /// A sanma hand cannot hold 2m to 8m or have them as dora indicators, and nukidora only exist in sanma.
+    pub fn check_hand(&self, hand: &Hand, context: &WinContext) -> Result<(), HandErr> {
+        if hand.aka_count() > self.aka_dora.into() {
+            return Err(HandErr::TooManyAkaDora);
+        }
+        if self.sanma.is_none() {
+            if context.nukidora() != 0 {
+                return Err(HandErr::NukidoraWithoutSanma);
+            }
+            return Ok(());
+        }
+
+        let indicators = context.dora().iter().chain(context.ura_dora());
+        if hand
+            .groups()
+            .iter()
+            .chain(indicators)
+            .any(TileGroup::is_sanma_excluded)
+        {
+            return Err(HandErr::InvalidSanmaTile);
+        }
+        if u32::from(tile_counts(hand.groups())[NORTH_INDEX]) + context.nukidora() > 4 {
+            return Err(HandErr::InvalidShape);
+        }
+        Ok(())
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::majsoul()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand::test_hand;
+
+    #[test]
+    fn presets_by_name() {
+        for name in PRESETS {
+            assert!(Ruleset::preset(name).is_some());
+        }
+        assert_eq!(Ruleset::preset("tenhou"), Some(Ruleset::tenhou()));
+        assert_eq!(Ruleset::preset("mleague"), None);
+        assert_eq!(Ruleset::default(), Ruleset::majsoul());
+    }
+
+    #[test]
+    fn sanma_tiles() {
+        let context = WinContext::default();
+        let manzu = test_hand(&["234m", "456p", "789s", "EEEw", "11s"], "1s", "Ew", "Ew");
+        let terminals = test_hand(&["111m", "456p", "789s", "NNNw", "11s"], "1s", "Ew", "Ew");
+        let sanma = Ruleset::tenhou_sanma();
+
+        assert_eq!(Ruleset::default().check_hand(&manzu, &context), Ok(()));
+        assert_eq!(
+            sanma.check_hand(&manzu, &context),
+            Err(HandErr::InvalidSanmaTile)
+        );
+        assert_eq!(sanma.check_hand(&terminals, &context), Ok(()));
+
+        let indicator = WinContext::builder()
+            .dora(vec!["5m".to_string().try_into().unwrap()])
+            .build()
+            .unwrap();
+        assert_eq!(
+            sanma.check_hand(&terminals, &indicator),
+            Err(HandErr::InvalidSanmaTile)
+        );
+
+        let nukidora = WinContext::builder().nukidora(1).build().unwrap();
+        assert_eq!(sanma.check_hand(&terminals, &nukidora), Ok(()));
+        assert_eq!(
+            Ruleset::default().check_hand(&terminals, &nukidora),
+            Err(HandErr::NukidoraWithoutSanma)
+        );
+        let nukidora = WinContext::builder().nukidora(2).build().unwrap();
+        assert_eq!(
+            sanma.check_hand(&terminals, &nukidora),
+            Err(HandErr::InvalidShape)
+        );
+    }
+}
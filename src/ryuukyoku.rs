@@ -0,0 +1,138 @@
+use crate::hand::error::HandErr;
+use crate::ruleset::Ruleset;
+use crate::score::HonbaCounter;
+use crate::settlement::{seat_index, PointDelta};
+use crate::tenpai::wait_tiles;
+use crate::tile_group::TileGroup;
+
+/// The result of an exhaustive draw (ryuukyoku), when the wall runs out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ryuukyoku {
+    /// Noten payments (noten bappu) of each seat, in the order of [`crate::settlement::SEATS`].
+    pub deltas: Vec<PointDelta>,
+    /// The dealer was tenpai and keeps the seat (renchan).
+    pub dealer_stays: bool,
+    /// Number of honba for the next hand.
+    pub honba: HonbaCounter,
+}
+
+/// Settle an exhaustive draw from the seat winds of the tenpai players.
+///
+/// The noten players split [`Ruleset::noten_bappu`] between them and pay it to the tenpai players, nothing changes
+/// hands when every player or no player is tenpai. A honba is always added, and the dealer only keeps the seat when tenpai.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::ruleset::Ruleset;
+/// use mahc::ryuukyoku::exhaustive_draw;
+/// use mahc::tile_group::TileGroup;
+///
+/// let south: TileGroup = "Sw".to_string().try_into().unwrap();
+/// let draw = exhaustive_draw(&[south], 2, &Ruleset::default()).unwrap();
+/// assert_eq!(draw.deltas, [-1_000, 3_000, -1_000, -1_000]);
+/// assert!(!draw.dealer_stays);
+/// assert_eq!(draw.honba, 3);
+/// ```
+pub fn exhaustive_draw(
+    tenpai: &[TileGroup],
+    honba: HonbaCounter,
+    rules: &Ruleset,
+) -> Result<Ryuukyoku, HandErr> {
+    let players = if rules.sanma.is_some() { 3 } else { 4 };
+    let mut is_tenpai = vec![false; players];
+    for seat in tenpai {
+        let seat = seat_index(seat)?;
+        if seat >= players || is_tenpai[seat] {
+            return Err(HandErr::InvalidSeat);
+        }
+        is_tenpai[seat] = true;
+    }
+
+    let tenpai_count = tenpai.len() as PointDelta;
+    let noten_count = players as PointDelta - tenpai_count;
+    let deltas = if tenpai_count == 0 || noten_count == 0 {
+        vec![0; players]
+    } else {
+        let bappu = rules.noten_bappu as PointDelta;
+        is_tenpai
+            .iter()
+            .map(|tenpai| match tenpai {
+                true => bappu / tenpai_count,
+                false => -bappu / noten_count,
+            })
+            .collect()
+    };
+
+    Ok(Ryuukyoku {
+        deltas,
+        dealer_stays: is_tenpai[0],
+        honba: honba + 1,
+    })
+}
+
+/// Check a hand claimed tenpai at a draw really is, returning the tiles it waits on.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::decompose::parse_tiles;
+/// use mahc::hand::error::HandErr;
+/// use mahc::ryuukyoku::check_tenpai;
+///
+/// let tenpai = parse_tiles("23m456p789s11222z").unwrap();
+/// assert_eq!(check_tenpai(&tenpai, &[]).unwrap().len(), 2);
+///
+/// let noten = parse_tiles("29m456p789s11222z").unwrap();
+/// assert_eq!(check_tenpai(&noten, &[]), Err(HandErr::NotTenpai));
+/// ```
+pub fn check_tenpai(closed: &[TileGroup], calls: &[TileGroup]) -> Result<Vec<TileGroup>, HandErr> {
+    let waits = wait_tiles(closed, calls)?;
+    if waits.is_empty() {
+        return Err(HandErr::NotTenpai);
+    }
+    Ok(waits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seats(seats: &[&str]) -> Vec<TileGroup> {
+        seats
+            .iter()
+            .map(|seat| seat.to_string().try_into().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn noten_payments() {
+        let rules = Ruleset::default();
+        let draw = |tenpai: &[&str]| exhaustive_draw(&seats(tenpai), 0, &rules).unwrap();
+
+        assert_eq!(draw(&[]).deltas, [0, 0, 0, 0]);
+        assert!(!draw(&[]).dealer_stays);
+        assert_eq!(draw(&["Ew", "Ww"]).deltas, [1_500, -1_500, 1_500, -1_500]);
+        assert!(draw(&["Ew", "Ww"]).dealer_stays);
+        assert_eq!(
+            draw(&["Ew", "Sw", "Nw"]).deltas,
+            [1_000, 1_000, -3_000, 1_000]
+        );
+        assert_eq!(draw(&["Ew", "Sw", "Ww", "Nw"]).deltas, [0, 0, 0, 0]);
+
+        assert_eq!(
+            exhaustive_draw(&seats(&["Sw", "Sw"]), 0, &rules),
+            Err(HandErr::InvalidSeat)
+        );
+    }
+
+    #[test]
+    fn sanma_noten_payments() {
+        let rules = Ruleset::tenhou_sanma();
+        let draw = |tenpai: &[&str]| exhaustive_draw(&seats(tenpai), 0, &rules);
+
+        assert_eq!(draw(&["Sw"]).unwrap().deltas, [-1_000, 2_000, -1_000]);
+        assert_eq!(draw(&["Ew", "Sw"]).unwrap().deltas, [1_000, 1_000, -2_000]);
+        assert_eq!(draw(&["Nw"]), Err(HandErr::InvalidSeat));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::fu::Fu;
-use crate::payment::Payment;
+use crate::limit_hand::LimitHands;
+use crate::pao::Pao;
+use crate::payment::{Payment, Points, RIICHI_DEPOSIT};
 use crate::yaku::Yaku;
 
 /// Han value.
@@ -8,6 +10,28 @@ pub type HanValue = u32;
 pub type FuValue = u64;
 /// Number of honba (repeat counts).
 pub type HonbaCounter = u64;
+/// Number of riichi sticks (kyoutaku) on the table.
+pub type RiichiStickCounter = u64;
+
+/// Han from each kind of dora, listed apart in the score breakdown.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Dora {
+    /// From the dora indicators, kan dora included.
+    pub dora: u32,
+    /// From red fives (aka dora).
+    pub aka: u32,
+    /// From the ura dora indicators, only with riichi.
+    pub ura: u32,
+    /// From north tiles set aside in sanma (nukidora).
+    pub nukidora: u32,
+}
+
+impl Dora {
+    /// Get the total number of han from dora.
+    pub fn total(&self) -> u32 {
+        self.dora + self.aka + self.ura + self.nukidora
+    }
+}
 
 /// Detailed breakdown of the winning hand's score.
 #[derive(Debug)]
@@ -24,14 +48,21 @@ pub struct Score {
     fu_score: FuValue,
     /// Number of repeat counters.
     honba: HonbaCounter,
+    /// Number of riichi sticks the winner collects.
+    riichi_sticks: RiichiStickCounter,
     /// Is the hand open when it scored?
     is_open: bool,
-    /// total number of han from dora 
-    dora_count: u32,
+    /// Han from each kind of dora.
+    dora: Dora,
+    /// The limit the score was capped at, if any.
+    limit_hand: Option<LimitHands>,
+    /// The player liable for a yakuman, if any.
+    pao: Option<Pao>,
 }
 
 impl Score {
     /// Create a new [`Score`].
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         payment: Payment,
         yaku: Vec<Yaku>,
@@ -40,7 +71,9 @@ impl Score {
         fu_score: FuValue,
         honba: HonbaCounter,
         is_open: bool,
-        dora_count: u32,
+        dora: Dora,
+        limit_hand: Option<LimitHands>,
+        pao: Option<Pao>,
     ) -> Self {
         Self {
             payment,
@@ -49,11 +82,20 @@ impl Score {
             han,
             fu_score,
             honba,
+            riichi_sticks: 0,
             is_open,
-            dora_count,
+            dora,
+            limit_hand,
+            pao,
         }
     }
 
+    /// Set the number of riichi sticks (kyoutaku) on the table the winner collects.
+    pub fn with_riichi_sticks(mut self, riichi_sticks: RiichiStickCounter) -> Self {
+        self.riichi_sticks = riichi_sticks;
+        self
+    }
+
     /// Get the payment breakdown.
     pub fn payment(&self) -> &Payment {
         &self.payment
@@ -84,13 +126,55 @@ impl Score {
         self.honba
     }
 
+    /// Get the number of riichi sticks the winner collects.
+    pub fn riichi_sticks(&self) -> RiichiStickCounter {
+        self.riichi_sticks
+    }
+
+    /// Get the total the winner receives, honba and riichi sticks included.
+    pub fn received(&self, dealer: bool, tsumo: bool) -> Points {
+        self.checked_received(dealer, tsumo)
+            .expect("payment is too large to count")
+    }
+
+    /// Get the total the winner receives, honba and riichi sticks included, or `None` if it would overflow.
+    pub fn checked_received(&self, dealer: bool, tsumo: bool) -> Option<Points> {
+        let riichi_sticks = self.riichi_sticks.checked_mul(RIICHI_DEPOSIT)?;
+        self.payment
+            .checked_total(dealer, tsumo, self.honba)?
+            .checked_add(riichi_sticks)
+    }
+
+    /// Check every payment of the score can be counted, honba and riichi sticks included.
+    pub fn is_countable(&self) -> bool {
+        self.payment.is_countable(self.honba)
+            && [(true, false), (true, true), (false, false), (false, true)]
+                .into_iter()
+                .all(|(dealer, tsumo)| self.checked_received(dealer, tsumo).is_some())
+    }
+
     /// Get the state of whether or not the hand was opened.
     pub fn is_open(&self) -> bool {
         self.is_open
     }
 
-    /// Get the total number of han from dora. 
-    pub fn dora_count(&self) -> u32{
-        self.dora_count
+    /// Get the total number of han from dora.
+    pub fn dora_count(&self) -> u32 {
+        self.dora.total()
+    }
+
+    /// Get the han from each kind of dora.
+    pub fn dora(&self) -> Dora {
+        self.dora
+    }
+
+    /// Get the limit the score was capped at (mangan and up), or `None` when it was counted from han and fu.
+    pub fn limit_hand(&self) -> Option<LimitHands> {
+        self.limit_hand
+    }
+
+    /// Get the player liable (pao) for a yakuman in the hand, their part is in [`Payment::pao_base_points`].
+    pub fn pao(&self) -> Option<&Pao> {
+        self.pao.as_ref()
     }
 }
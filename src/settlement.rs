@@ -0,0 +1,578 @@
+use crate::calc::get_hand_score;
+use crate::context::WinContext;
+use crate::hand::error::HandErr;
+use crate::hand::Hand;
+use crate::payment::{Points, RIICHI_DEPOSIT};
+use crate::ruleset::Ruleset;
+use crate::score::Score;
+use crate::suit::Suit;
+use crate::tile_group::TileGroup;
+
+/// Change in a player's points, negative when they pay.
+pub type PointDelta = i64;
+
+/// Seat winds in turn order, east is the dealer.
+pub const SEATS: [&str; 4] = ["Ew", "Sw", "Ww", "Nw"];
+
+/// The points every player gains or loses from a win.
+///
+/// The deltas add up to the riichi sticks the winner collects from the table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settlement {
+    /// Point change of each seat in the order of [`SEATS`], without north in sanma.
+    pub deltas: Vec<PointDelta>,
+    /// Points the winner collects from riichi sticks left on the table.
+    pub riichi_sticks: Points,
+}
+
+impl Settlement {
+    /// Get the point change of a seat.
+    pub fn delta(&self, seat: &TileGroup) -> Result<PointDelta, HandErr> {
+        self.deltas
+            .get(seat_index(seat)?)
+            .copied()
+            .ok_or(HandErr::InvalidSeat)
+    }
+
+    /// Get the total points a seat received, zero for the players who paid.
+    pub fn received(&self, seat: &TileGroup) -> Result<Points, HandErr> {
+        Ok(self.delta(seat)?.max(0) as Points)
+    }
+}
+
+/// Convert points paid into a point change, failing when they are too large to count.
+pub(crate) fn to_delta(points: Points) -> Result<PointDelta, HandErr> {
+    PointDelta::try_from(points).map_err(|_| HandErr::ScoreOverflow)
+}
+
+/// Add a point change to a total, failing when the total is too large to count.
+pub(crate) fn add_delta(total: &mut PointDelta, delta: PointDelta) -> Result<(), HandErr> {
+    *total = total.checked_add(delta).ok_or(HandErr::ScoreOverflow)?;
+    Ok(())
+}
+
+/// Get the position of a seat wind in turn order, east first.
+pub fn seat_index(seat: &TileGroup) -> Result<usize, HandErr> {
+    if seat.suit != Suit::Wind {
+        return Err(HandErr::InvalidSeat);
+    }
+    SEATS
+        .iter()
+        .position(|wind| *wind == seat.to_string())
+        .ok_or(HandErr::InvalidSeat)
+}
+
+/// Work out the point transfers for a scored hand.
+///
+/// `loser` is the seat that dealt in on a ron and `None` on a tsumo. The east seat is the dealer.
+/// Honba are part of the payments and the riichi sticks of the score go to the winner.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::calc::get_hand_score;
+/// use mahc::context::WinContext;
+/// use mahc::hand::Hand;
+/// use mahc::ruleset::Ruleset;
+/// use mahc::settlement::settle;
+/// use mahc::tile_group::TileGroup;
+///
+/// let hand = Hand::new_from_strings(
+///     vec!["123m".to_string(), "456p".to_string(), "789s".to_string(), "SSSw".to_string(), "11s".to_string()],
+///     "1s".to_string(),
+///     "Ew".to_string(),
+///     "Sw".to_string(),
+/// )
+/// .unwrap();
+/// let context = WinContext::builder().honba(1).riichi_sticks(2).build().unwrap();
+/// let rules = Ruleset::default();
+/// let score = get_hand_score(hand, &context, &rules).unwrap();
+///
+/// let south: TileGroup = "Sw".to_string().try_into().unwrap();
+/// let north: TileGroup = "Nw".to_string().try_into().unwrap();
+/// let settlement = settle(&score, &south, Some(&north), &rules).unwrap();
+/// assert_eq!(settlement.deltas, [0, 1_300 + 300 + 2_000, 0, -1_600]);
+/// ```
+pub fn settle(
+    score: &Score,
+    winner: &TileGroup,
+    loser: Option<&TileGroup>,
+    rules: &Ruleset,
+) -> Result<Settlement, HandErr> {
+    let players = if rules.sanma.is_some() { 3 } else { 4 };
+    let winner = seat_index(winner)?;
+    let loser = loser.map(seat_index).transpose()?;
+    if winner >= players || loser.is_some_and(|loser| loser >= players || loser == winner) {
+        return Err(HandErr::InvalidSeat);
+    }
+
+    let payment = score.payment();
+    let honba = score.honba();
+    let dealer = winner == 0;
+    let pao = score.pao().map(|pao| seat_index(&pao.seat)).transpose()?;
+    if pao.is_some_and(|pao| pao >= players || pao == winner) {
+        return Err(HandErr::InvalidSeat);
+    }
+
+    let mut paid: [Points; 4] = [0; 4];
+    match (loser, pao) {
+        (Some(loser), None) => {
+            paid[loser] = if dealer {
+                payment.checked_dealer_ron(honba)
+            } else {
+                payment.checked_non_dealer_ron(honba)
+            }
+            .ok_or(HandErr::ScoreOverflow)?;
+        }
+        (None, None) => {
+            for (seat, amount) in paid.iter_mut().enumerate().take(players) {
+                *amount = match (dealer, seat) {
+                    (_, seat) if seat == winner => Some(0),
+                    (true, _) => payment.checked_dealer_tsumo(honba),
+                    (false, 0) => payment.checked_non_dealer_tsumo_to_dealer(honba),
+                    (false, _) => payment.checked_non_dealer_tsumo_to_non_dealer(honba),
+                }
+                .ok_or(HandErr::ScoreOverflow)?;
+            }
+        }
+        (Some(loser), Some(liable)) => {
+            let split = payment
+                .checked_pao(dealer, false, honba)
+                .ok_or(HandErr::ScoreOverflow)?;
+            paid[loser] = split.discarder;
+            paid[liable] = paid[liable]
+                .checked_add(split.liable)
+                .ok_or(HandErr::ScoreOverflow)?;
+        }
+        (None, Some(liable)) => {
+            let split = payment
+                .checked_pao(dealer, true, honba)
+                .ok_or(HandErr::ScoreOverflow)?;
+            for (seat, amount) in paid.iter_mut().enumerate().take(players) {
+                *amount = match seat {
+                    seat if seat == winner => 0,
+                    0 => split.dealer,
+                    _ => split.non_dealer,
+                };
+            }
+            paid[liable] = paid[liable]
+                .checked_add(split.liable)
+                .ok_or(HandErr::ScoreOverflow)?;
+        }
+    }
+
+    let riichi_sticks = score
+        .riichi_sticks()
+        .checked_mul(RIICHI_DEPOSIT)
+        .ok_or(HandErr::ScoreOverflow)?;
+    let mut deltas: Vec<PointDelta> = vec![0; players];
+    for (seat, amount) in paid.iter().enumerate().take(players) {
+        let amount = to_delta(*amount)?;
+        add_delta(&mut deltas[seat], -amount)?;
+        add_delta(&mut deltas[winner], amount)?;
+    }
+    add_delta(&mut deltas[winner], to_delta(riichi_sticks)?)?;
+
+    Ok(Settlement {
+        deltas,
+        riichi_sticks,
+    })
+}
+
+/// The outcome of several players calling ron on the same discard.
+#[derive(Debug)]
+pub enum MultipleRon {
+    /// The players who won in turn order from the discarder, with their scores and the combined settlement.
+    Wins {
+        /// Seat wind and score of each winner.
+        winners: Vec<(TileGroup, Score)>,
+        /// The point change of every player from all the wins.
+        settlement: Settlement,
+    },
+    /// Three players called ron and the ruleset makes it an abortive draw.
+    AbortiveDraw,
+}
+
+/// Score every player who called ron on the discard of `discarder`.
+///
+/// Each win is the hand, with the seat wind of the winner, and its context. With [`Ruleset::atamahane`] only the first
+/// winner in turn order from the discarder wins, otherwise they all do and the honba and riichi sticks go to the first.
+/// The honba and riichi sticks are taken from the context of that winner.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::context::WinContext;
+/// use mahc::hand::Hand;
+/// use mahc::ruleset::Ruleset;
+/// use mahc::settlement::{multiple_ron, MultipleRon};
+///
+/// let west = Hand::new_from_strings(
+///     vec!["123m".to_string(), "456p".to_string(), "789s".to_string(), "WWWw".to_string(), "11s".to_string()],
+///     "1s".to_string(),
+///     "Ew".to_string(),
+///     "Ww".to_string(),
+/// )
+/// .unwrap();
+/// let north = Hand::new_from_strings(
+///     vec!["123m".to_string(), "456p".to_string(), "789s".to_string(), "NNNw".to_string(), "11s".to_string()],
+///     "1s".to_string(),
+///     "Ew".to_string(),
+///     "Nw".to_string(),
+/// )
+/// .unwrap();
+/// let context = WinContext::builder().honba(1).build().unwrap();
+/// let wins = vec![(west, context.clone()), (north, context)];
+/// let discarder = "Sw".to_string().try_into().unwrap();
+///
+/// let out = multiple_ron(wins.clone(), &discarder, &Ruleset::majsoul()).unwrap();
+/// let MultipleRon::Wins { settlement, .. } = out else { panic!() };
+/// assert_eq!(settlement.deltas, [0, -1_600 - 1_300, 1_600, 1_300]);
+///
+/// let out = multiple_ron(wins, &discarder, &Ruleset::wrc()).unwrap();
+/// let MultipleRon::Wins { settlement, .. } = out else { panic!() };
+/// assert_eq!(settlement.deltas, [0, -1_600, 1_600, 0]);
+/// ```
+pub fn multiple_ron(
+    wins: Vec<(Hand, WinContext)>,
+    discarder: &TileGroup,
+    rules: &Ruleset,
+) -> Result<MultipleRon, HandErr> {
+    let players = if rules.sanma.is_some() { 3 } else { 4 };
+    let seats: Vec<TileGroup> = wins.iter().map(|(hand, _)| hand.seat_tile()).collect();
+    let closest = closest_winner(discarder, &seats)?;
+    for (i, seat) in seats.iter().enumerate() {
+        if seats[..i].contains(seat) {
+            return Err(HandErr::InvalidSeat);
+        }
+    }
+    if wins.iter().any(|(_, context)| context.tsumo()) {
+        return Err(HandErr::MultipleRonTsumo);
+    }
+    if wins.len() == 3 && players == 4 && rules.triple_ron_draw {
+        return Ok(MultipleRon::AbortiveDraw);
+    }
+
+    let discarder_index = seat_index(discarder)?;
+    let mut wins = wins;
+    wins.sort_by_key(|(hand, _)| {
+        seat_index(&hand.seat_tile()).map_or(0, |seat| {
+            (seat + SEATS.len() - discarder_index) % SEATS.len()
+        })
+    });
+    if rules.atamahane {
+        wins.truncate(1);
+    }
+
+    let mut winners = vec![];
+    let mut settlement = Settlement {
+        deltas: vec![0; players],
+        riichi_sticks: 0,
+    };
+    for (hand, context) in wins {
+        let seat = hand.seat_tile();
+        let context = if seat == closest {
+            context
+        } else {
+            context.to_builder().honba(0).riichi_sticks(0).build()?
+        };
+        let score = get_hand_score(hand, &context, rules)?;
+        let win = settle(&score, &seat, Some(discarder), rules)?;
+        for (total, delta) in settlement.deltas.iter_mut().zip(win.deltas) {
+            add_delta(total, delta)?;
+        }
+        settlement.riichi_sticks += win.riichi_sticks;
+        winners.push((seat, score));
+    }
+
+    Ok(MultipleRon::Wins {
+        winners,
+        settlement,
+    })
+}
+
+/// Find the winner of a multiple ron who takes the riichi sticks, the first after the discarder in turn order.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::settlement::closest_winner;
+/// use mahc::tile_group::TileGroup;
+///
+/// let seat = |seat: &str| -> TileGroup { seat.to_string().try_into().unwrap() };
+/// let winner = closest_winner(&seat("Ww"), &[seat("Sw"), seat("Ew")]).unwrap();
+/// assert_eq!(winner, seat("Ew"));
+/// ```
+pub fn closest_winner(discarder: &TileGroup, winners: &[TileGroup]) -> Result<TileGroup, HandErr> {
+    let discarder = seat_index(discarder)?;
+    let mut closest: Option<(usize, &TileGroup)> = None;
+    for winner in winners {
+        let distance = (seat_index(winner)? + SEATS.len() - discarder) % SEATS.len();
+        if distance == 0 {
+            return Err(HandErr::InvalidSeat);
+        }
+        if closest.is_none_or(|(closest, _)| distance < closest) {
+            closest = Some((distance, winner));
+        }
+    }
+    closest
+        .map(|(_, winner)| winner.clone())
+        .ok_or(HandErr::InvalidSeat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::CalledMeld;
+    use crate::hand::test_hand;
+    use crate::payment::Payment;
+    use crate::score::Dora;
+
+    fn seat(seat: &str) -> TileGroup {
+        seat.to_string().try_into().unwrap()
+    }
+
+    fn score(groups: &[&str], win: &str, seat: &str, context: &WinContext) -> Score {
+        let hand = test_hand(groups, win, "Ew", seat);
+        get_hand_score(hand, context, &Ruleset::default()).unwrap()
+    }
+
+    #[test]
+    fn tsumo_settlement() {
+        let context = WinContext::builder()
+            .tsumo(true)
+            .honba(2)
+            .riichi_sticks(1)
+            .build()
+            .unwrap();
+        // menzen tsumo and south wind, 2 han 40 fu with 2 honba
+        let south = score(
+            &["123m", "456p", "789s", "SSSw", "11s"],
+            "1s",
+            "Sw",
+            &context,
+        );
+        let out = settle(&south, &seat("Sw"), None, &Ruleset::default()).unwrap();
+        assert_eq!(out.deltas, [-1_500, 3_300 + 1_000, -900, -900]);
+        assert_eq!(out.received(&seat("Sw")).unwrap(), 4_300);
+        assert_eq!(out.received(&seat("Ew")).unwrap(), 0);
+
+        let east = score(
+            &["123m", "456p", "789s", "EEEw", "11s"],
+            "1s",
+            "Ew",
+            &context,
+        );
+        let out = settle(&east, &seat("Ew"), None, &Ruleset::default()).unwrap();
+        assert_eq!(out.deltas, [8_400 + 1_000, -2_800, -2_800, -2_800]);
+    }
+
+    #[test]
+    fn pao_settlement() {
+        let call = |meld: &str, from: &str| CalledMeld {
+            meld: meld.to_string().try_into().unwrap(),
+            from: Some(seat(from)),
+        };
+        let context = WinContext::builder()
+            .called_from(vec![
+                call("wwwdo", "Ew"),
+                call("gggdo", "Ww"),
+                call("rrrdo", "Nw"),
+            ])
+            .build()
+            .unwrap();
+        let daisangen = score(
+            &["wwwdo", "gggdo", "rrrdo", "EEEw", "99p"],
+            "9p",
+            "Sw",
+            &context,
+        );
+
+        let out = settle(
+            &daisangen,
+            &seat("Sw"),
+            Some(&seat("Ww")),
+            &Ruleset::default(),
+        );
+        assert_eq!(out.unwrap().deltas, [0, 32_000, -16_000, -16_000]);
+
+        // the liable player dealt in themselves
+        let out = settle(
+            &daisangen,
+            &seat("Sw"),
+            Some(&seat("Nw")),
+            &Ruleset::default(),
+        );
+        assert_eq!(out.unwrap().deltas, [0, 32_000, 0, -32_000]);
+
+        let tsumo = context.to_builder().tsumo(true).build().unwrap();
+        let daisangen = score(
+            &["wwwdo", "gggdo", "rrrdo", "EEEw", "99p"],
+            "9p",
+            "Sw",
+            &tsumo,
+        );
+        let out = settle(&daisangen, &seat("Sw"), None, &Ruleset::default());
+        assert_eq!(out.unwrap().deltas, [0, 32_000, 0, -32_000]);
+    }
+
+    #[test]
+    fn multiple_ron_sticks() {
+        let winners = [seat("Ew"), seat("Ww")];
+        assert_eq!(closest_winner(&seat("Sw"), &winners), Ok(seat("Ww")));
+        assert_eq!(closest_winner(&seat("Nw"), &winners), Ok(seat("Ew")));
+        assert_eq!(
+            closest_winner(&seat("Ew"), &winners),
+            Err(HandErr::InvalidSeat)
+        );
+        assert_eq!(closest_winner(&seat("Ew"), &[]), Err(HandErr::InvalidSeat));
+    }
+
+    #[test]
+    fn multiple_ron_settlement() {
+        let context = WinContext::builder()
+            .honba(1)
+            .riichi_sticks(2)
+            .build()
+            .unwrap();
+        let east = (
+            test_hand(&["123m", "456p", "789s", "EEEw", "11s"], "1s", "Ew", "Ew"),
+            context.clone(),
+        );
+        let south = (
+            test_hand(&["123m", "456p", "789s", "SSSw", "11s"], "1s", "Ew", "Sw"),
+            context.clone(),
+        );
+        let west = (
+            test_hand(&["123m", "456p", "789s", "WWWw", "11s"], "1s", "Ew", "Ww"),
+            context.clone(),
+        );
+
+        // sticks and honba go to south, the first after north
+        let out = multiple_ron(
+            vec![east.clone(), south.clone()],
+            &seat("Nw"),
+            &Ruleset::majsoul(),
+        );
+        let Ok(MultipleRon::Wins {
+            winners,
+            settlement,
+        }) = out
+        else {
+            panic!("expected a double ron");
+        };
+        assert_eq!(winners[0].0, seat("Ew"));
+        assert_eq!(winners[1].0, seat("Sw"));
+        assert_eq!(winners[1].1.honba(), 0);
+        assert_eq!(settlement.deltas, [3_900 + 300 + 2_000, 1_300, 0, -5_500]);
+        assert_eq!(settlement.riichi_sticks, 2_000);
+
+        // east is the first after west, so south is head bumped
+        let out = multiple_ron(
+            vec![south.clone(), east.clone()],
+            &seat("Ww"),
+            &Ruleset::wrc(),
+        );
+        let Ok(MultipleRon::Wins { settlement, .. }) = out else {
+            panic!("expected a single winner");
+        };
+        assert_eq!(settlement.deltas, [3_900 + 300 + 2_000, 0, -4_200, 0]);
+        assert!(matches!(
+            multiple_ron(
+                vec![east.clone(), south.clone(), west.clone()],
+                &seat("Nw"),
+                &Ruleset::tenhou()
+            ),
+            Ok(MultipleRon::AbortiveDraw)
+        ));
+
+        let tsumo = (west.0, context.to_builder().tsumo(true).build().unwrap());
+        assert_eq!(
+            multiple_ron(vec![east.clone(), tsumo], &seat("Nw"), &Ruleset::majsoul()).unwrap_err(),
+            HandErr::MultipleRonTsumo
+        );
+        assert_eq!(
+            multiple_ron(vec![east.clone(), east], &seat("Nw"), &Ruleset::majsoul()).unwrap_err(),
+            HandErr::InvalidSeat
+        );
+    }
+
+    #[test]
+    fn invalid_seats() {
+        let context = WinContext::default();
+        let hand = score(
+            &["123m", "456p", "789s", "SSSw", "11s"],
+            "1s",
+            "Sw",
+            &context,
+        );
+        let rules = Ruleset::default();
+        for (winner, loser) in [("Sw", Some("Sw")), ("5m", Some("Ew")), ("Sw", Some("rd"))] {
+            let loser = loser.map(seat);
+            let out = settle(&hand, &seat(winner), loser.as_ref(), &rules);
+            assert_eq!(out.unwrap_err(), HandErr::InvalidSeat);
+        }
+        let out = settle(
+            &hand,
+            &seat("Sw"),
+            Some(&seat("Nw")),
+            &Ruleset::tenhou_sanma(),
+        );
+        assert_eq!(out.unwrap_err(), HandErr::InvalidSeat);
+    }
+
+    #[test]
+    fn overflowing_settlement() {
+        let score = |base_points: Points| {
+            Score::new(
+                Payment::new(base_points),
+                vec![],
+                vec![],
+                1,
+                30,
+                0,
+                false,
+                Dora::default(),
+                None,
+                None,
+            )
+        };
+        // a non-dealer ron of exactly the largest payment that fits
+        let largest = score(2_305_843_009_213_693_950);
+        let out = settle(
+            &largest,
+            &seat("Sw"),
+            Some(&seat("Ww")),
+            &Ruleset::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            out.deltas,
+            [0, 9_223_372_036_854_775_800, -9_223_372_036_854_775_800, 0]
+        );
+
+        let too_large = score(2_305_843_009_213_693_975);
+        let out = settle(
+            &too_large,
+            &seat("Sw"),
+            Some(&seat("Ww")),
+            &Ruleset::default(),
+        );
+        assert_eq!(out.unwrap_err(), HandErr::ScoreOverflow);
+
+        // the riichi sticks push the winner over
+        let out = settle(
+            &largest.with_riichi_sticks(1),
+            &seat("Sw"),
+            Some(&seat("Ww")),
+            &Ruleset::default(),
+        );
+        assert_eq!(out.unwrap_err(), HandErr::ScoreOverflow);
+
+        // so many riichi sticks that counting them overflows
+        let sticks = score(2_000).with_riichi_sticks(100_000_000_000_000_000);
+        assert_eq!(sticks.checked_received(false, false), None);
+        let out = settle(&sticks, &seat("Sw"), None, &Ruleset::default());
+        assert_eq!(out.unwrap_err(), HandErr::ScoreOverflow);
+    }
+}
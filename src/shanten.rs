@@ -0,0 +1,254 @@
+use crate::decompose::ORPHAN_INDICES;
+use crate::hand::error::HandErr;
+use crate::tile_group::{tile_counts, GroupType, TileGroup, TILE_KINDS};
+
+/// Number of tiles a hand is away from tenpai, for each hand shape.
+///
+/// A shanten of 0 is tenpai and -1 is a complete hand.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::decompose::parse_tiles;
+/// use mahc::shanten::Shanten;
+///
+/// let closed = parse_tiles("123m456p789s1122z").unwrap();
+/// let shanten = Shanten::new(&closed, &[]).unwrap();
+/// assert_eq!(shanten.standard, 0);
+/// assert_eq!(shanten.chiitoitsu, Some(4));
+/// assert_eq!(shanten.kokushi, Some(8));
+/// assert_eq!(shanten.min(), 0);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Shanten {
+    /// Shanten for four groups and a pair.
+    pub standard: i8,
+    /// Shanten for seven pairs, only possible without calls.
+    pub chiitoitsu: Option<i8>,
+    /// Shanten for kokushi musou, only possible without calls.
+    pub kokushi: Option<i8>,
+}
+
+impl Shanten {
+    /// Calculate the shanten of the concealed tiles and called melds (13 or 14 tiles in total).
+    pub fn new(closed: &[TileGroup], calls: &[TileGroup]) -> Result<Self, HandErr> {
+        let counts = hand_counts(closed, calls)?;
+
+        Ok(Self::from_counts(&counts, calls.len()))
+    }
+
+    /// Calculate the shanten from the counts of the concealed tiles and the number of called melds.
+    pub fn from_counts(counts: &[u8; TILE_KINDS], calls: usize) -> Self {
+        let closed_only = calls == 0;
+        Self {
+            standard: standard_shanten(counts, calls),
+            chiitoitsu: closed_only.then(|| chiitoitsu_shanten(counts)),
+            kokushi: closed_only.then(|| kokushi_shanten(counts)),
+        }
+    }
+
+    /// Get the lowest shanten of every hand shape.
+    pub fn min(&self) -> i8 {
+        [Some(self.standard), self.chiitoitsu, self.kokushi]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap()
+    }
+}
+
+/// Count the concealed tiles, checking the hand holds 13 or 14 tiles and no more than four of a kind.
+pub(crate) fn hand_counts(
+    closed: &[TileGroup],
+    calls: &[TileGroup],
+) -> Result<[u8; TILE_KINDS], HandErr> {
+    if calls
+        .iter()
+        .any(|call| matches!(call.group_type, GroupType::Pair | GroupType::None))
+    {
+        return Err(HandErr::InvalidGroup);
+    }
+
+    let counts = tile_counts(closed);
+    let tile_count = counts.iter().sum::<u8>() as usize + calls.len() * 3;
+    if calls.len() > 4 || !(13..=14).contains(&tile_count) {
+        return Err(HandErr::InvalidShape);
+    }
+
+    let called = tile_counts(calls);
+    if (0..TILE_KINDS).any(|i| counts[i] + called[i] > 4) {
+        return Err(HandErr::InvalidShape);
+    }
+
+    Ok(counts)
+}
+
+/// Shanten for a hand of four groups and a pair, with `calls` groups already called.
+pub fn standard_shanten(counts: &[u8; TILE_KINDS], calls: usize) -> i8 {
+    let mut counts = *counts;
+    let mut best = 8;
+
+    for pair in 0..TILE_KINDS {
+        if counts[pair] >= 2 {
+            counts[pair] -= 2;
+            search_groups(&mut counts, 0, calls, 0, 1, &mut best);
+            counts[pair] += 2;
+        }
+    }
+    search_groups(&mut counts, 0, calls, 0, 0, &mut best);
+
+    best
+}
+
+/// Take complete groups and partial groups (pairs and incomplete sequences) from the counts, keeping the lowest shanten.
+fn search_groups(
+    counts: &mut [u8; TILE_KINDS],
+    start: usize,
+    sets: usize,
+    partials: usize,
+    pair: usize,
+    best: &mut i8,
+) {
+    let Some(i) = (start..TILE_KINDS).find(|&i| counts[i] > 0) else {
+        // only four groups count, any extra partial groups are useless
+        let partials = partials.min(4usize.saturating_sub(sets));
+        let shanten = 8 - (2 * sets + partials + pair) as i8;
+        *best = (*best).min(shanten);
+        return;
+    };
+    let suited = i < 27;
+
+    if counts[i] >= 3 {
+        counts[i] -= 3;
+        search_groups(counts, i, sets + 1, partials, pair, best);
+        counts[i] += 3;
+    }
+    if suited && i % 9 <= 6 && counts[i + 1] > 0 && counts[i + 2] > 0 {
+        take(counts, &[i, i + 1, i + 2]);
+        search_groups(counts, i, sets + 1, partials, pair, best);
+        give(counts, &[i, i + 1, i + 2]);
+    }
+
+    if sets + partials < 4 {
+        if counts[i] >= 2 {
+            counts[i] -= 2;
+            search_groups(counts, i, sets, partials + 1, pair, best);
+            counts[i] += 2;
+        }
+        if suited && i % 9 <= 7 && counts[i + 1] > 0 {
+            take(counts, &[i, i + 1]);
+            search_groups(counts, i, sets, partials + 1, pair, best);
+            give(counts, &[i, i + 1]);
+        }
+        if suited && i % 9 <= 6 && counts[i + 2] > 0 {
+            take(counts, &[i, i + 2]);
+            search_groups(counts, i, sets, partials + 1, pair, best);
+            give(counts, &[i, i + 2]);
+        }
+    }
+
+    // leave the tile isolated
+    counts[i] -= 1;
+    search_groups(counts, i, sets, partials, pair, best);
+    counts[i] += 1;
+}
+
+fn take(counts: &mut [u8; TILE_KINDS], tiles: &[usize]) {
+    tiles.iter().for_each(|&tile| counts[tile] -= 1);
+}
+
+fn give(counts: &mut [u8; TILE_KINDS], tiles: &[usize]) {
+    tiles.iter().for_each(|&tile| counts[tile] += 1);
+}
+
+/// Shanten for a hand of seven unique pairs.
+pub fn chiitoitsu_shanten(counts: &[u8; TILE_KINDS]) -> i8 {
+    let pairs = counts.iter().filter(|&&count| count >= 2).count() as i8;
+    let kinds = counts.iter().filter(|&&count| count >= 1).count() as i8;
+
+    6 - pairs + (7 - kinds).max(0)
+}
+
+/// Shanten for kokushi musou (thirteen orphans).
+pub fn kokushi_shanten(counts: &[u8; TILE_KINDS]) -> i8 {
+    let kinds = ORPHAN_INDICES.iter().filter(|&&i| counts[i] >= 1).count() as i8;
+    let has_pair = ORPHAN_INDICES.iter().any(|&i| counts[i] >= 2);
+
+    13 - kinds - has_pair as i8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decompose::parse_tiles;
+
+    fn shanten(closed: &str, calls: &[&str]) -> Result<Shanten, HandErr> {
+        let closed = parse_tiles(closed).unwrap();
+        let calls: Vec<TileGroup> = calls
+            .iter()
+            .map(|call| call.to_string().try_into().unwrap())
+            .collect();
+        Shanten::new(&closed, &calls)
+    }
+
+    #[test]
+    fn standard_shanten_values() {
+        assert_eq!(shanten("123m456p789s1122z", &[]).unwrap().standard, 0);
+        assert_eq!(shanten("123m456p789s11222z", &[]).unwrap().standard, -1);
+        assert_eq!(shanten("147m258p369s1234z", &[]).unwrap().standard, 8);
+        assert_eq!(shanten("13m46p79s123m55z12z", &[]).unwrap().standard, 2);
+        assert_eq!(shanten("1112345678999m", &[]).unwrap().standard, 0);
+        assert_eq!(
+            shanten("111234567899m", &[]).unwrap_err(),
+            HandErr::InvalidShape
+        );
+        assert_eq!(shanten("1112345678999m", &[]).unwrap().chiitoitsu, Some(4));
+    }
+
+    #[test]
+    fn standard_shanten_with_calls() {
+        let out = shanten("2345m", &["rrrdo", "111po", "789so"]).unwrap();
+        assert_eq!(out.standard, 0);
+        assert_eq!(out.chiitoitsu, None);
+        assert_eq!(out.kokushi, None);
+
+        let out = shanten("2468m12p5s", &["rrrdo", "111po"]).unwrap();
+        assert_eq!(out.standard, 2);
+    }
+
+    #[test]
+    fn chiitoitsu_shanten_values() {
+        let out = shanten("1133557799m1122z", &[]).unwrap();
+        assert_eq!(out.chiitoitsu, Some(-1));
+        let out = shanten("113355779m11223z", &[]).unwrap();
+        assert_eq!(out.chiitoitsu, Some(0));
+        // four of a kind only counts as one pair
+        let out = shanten("1111m335577p223z", &[]).unwrap();
+        assert_eq!(out.chiitoitsu, Some(2));
+    }
+
+    #[test]
+    fn kokushi_shanten_values() {
+        let out = shanten("19m19p19s1234567z", &[]).unwrap();
+        assert_eq!(out.kokushi, Some(0));
+        let out = shanten("19m19p19s12345677z", &[]).unwrap();
+        assert_eq!(out.kokushi, Some(-1));
+        let out = shanten("19m19p19s1234566z", &[]).unwrap();
+        assert_eq!(out.kokushi, Some(0));
+        let out = shanten("123456789m12345p", &[]).unwrap();
+        assert_eq!(out.kokushi, Some(10));
+    }
+
+    #[test]
+    fn invalid_shanten_input() {
+        assert_eq!(
+            shanten("11111m23456789p", &[]).unwrap_err(),
+            HandErr::InvalidShape
+        );
+        assert_eq!(shanten("123m", &[]).unwrap_err(), HandErr::InvalidShape);
+        assert_eq!(
+            shanten("123m456p789s1z", &["11so"]).unwrap_err(),
+            HandErr::InvalidGroup
+        );
+    }
+}
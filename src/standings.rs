@@ -0,0 +1,183 @@
+use crate::hand::error::HandErr;
+use crate::settlement::PointDelta;
+
+/// How players who end on the same points are placed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TieBreak {
+    /// The player who sat first in turn order on the first hand places higher.
+    SeatOrder,
+    /// The players share the place and split its uma and oka.
+    Split,
+}
+
+/// The uma and oka added to the final points of a game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UmaOka {
+    /// Points added for each place, first place first.
+    pub uma: Vec<PointDelta>,
+    /// Points every player started with.
+    pub starting_points: PointDelta,
+    /// Points subtracted from every player, the difference to the starting points of every player is the oka for first place.
+    pub return_points: PointDelta,
+}
+
+impl UmaOka {
+    /// Uma and oka from the uma of the top half of the places, the bottom half pays the same back.
+    ///
+    /// Sanma takes the uma of first place, with second getting none.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::standings::UmaOka;
+    ///
+    /// let uma = UmaOka::symmetric(&[15_000, 5_000], 4, 25_000, 30_000).unwrap();
+    /// assert_eq!(uma.uma, [15_000, 5_000, -5_000, -15_000]);
+    ///
+    /// let sanma = UmaOka::symmetric(&[15_000], 3, 35_000, 40_000).unwrap();
+    /// assert_eq!(sanma.uma, [15_000, 0, -15_000]);
+    /// ```
+    pub fn symmetric(
+        top: &[PointDelta],
+        players: usize,
+        starting_points: PointDelta,
+        return_points: PointDelta,
+    ) -> Result<Self, HandErr> {
+        if top.len() != players / 2 {
+            return Err(HandErr::InvalidUma);
+        }
+        let mut uma = top.to_vec();
+        if players % 2 == 1 {
+            uma.push(0);
+        }
+        uma.extend(top.iter().rev().map(|uma| -uma));
+        Ok(Self {
+            uma,
+            starting_points,
+            return_points,
+        })
+    }
+
+    /// Get the oka given to first place.
+    pub fn oka(&self) -> PointDelta {
+        (self.return_points - self.starting_points) * self.uma.len() as PointDelta
+    }
+}
+
+/// The final result of one player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    /// The player, numbered from the first dealer.
+    pub player: usize,
+    /// Place from 1, tied players share a place when it is split.
+    pub place: usize,
+    /// Points at the end of the game.
+    pub points: PointDelta,
+    /// Final score in points, after the return points, uma and oka.
+    pub score: PointDelta,
+}
+
+/// Work out the final standings from every player's points at the end of a game, best first.
+///
+/// When a tie is split any points left over from sharing the uma go to the first tied player in seat order.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::standings::{standings, TieBreak, UmaOka};
+///
+/// let uma = UmaOka::symmetric(&[15_000, 5_000], 4, 25_000, 30_000).unwrap();
+/// let out = standings(&[18_000, 42_100, 31_900, 8_000], &uma, TieBreak::SeatOrder).unwrap();
+/// let scores: Vec<i64> = out.iter().map(|standing| standing.score).collect();
+/// assert_eq!(out[0].player, 1);
+/// assert_eq!(scores, [12_100 + 15_000 + 20_000, 1_900 + 5_000, -12_000 - 5_000, -22_000 - 15_000]);
+/// ```
+pub fn standings(
+    points: &[PointDelta],
+    uma_oka: &UmaOka,
+    tie_break: TieBreak,
+) -> Result<Vec<Standing>, HandErr> {
+    if uma_oka.uma.len() != points.len() {
+        return Err(HandErr::InvalidUma);
+    }
+
+    // a stable sort keeps seat order between tied players
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by_key(|player| -points[*player]);
+
+    let mut bonus: Vec<PointDelta> = uma_oka.uma.clone();
+    bonus[0] += uma_oka.oka();
+
+    let mut out = vec![];
+    let mut first = 0;
+    while first < order.len() {
+        let tied = match tie_break {
+            TieBreak::SeatOrder => 1,
+            TieBreak::Split => order[first..]
+                .iter()
+                .take_while(|player| points[**player] == points[order[first]])
+                .count(),
+        };
+        let shared: PointDelta = bonus[first..first + tied].iter().sum();
+        let share = shared.div_euclid(tied as PointDelta);
+        let leftover = shared - share * tied as PointDelta;
+
+        for (i, player) in order[first..first + tied].iter().enumerate() {
+            let points = points[*player];
+            let bonus = if i == 0 { share + leftover } else { share };
+            out.push(Standing {
+                player: *player,
+                place: first + 1,
+                points,
+                score: points - uma_oka.return_points + bonus,
+            });
+        }
+        first += tied;
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tied_places() {
+        let uma = UmaOka::symmetric(&[30_000, 10_000], 4, 25_000, 25_000).unwrap();
+        let points = [30_000, 20_000, 30_000, 20_000];
+
+        let seat_order = standings(&points, &uma, TieBreak::SeatOrder).unwrap();
+        let players: Vec<usize> = seat_order.iter().map(|standing| standing.player).collect();
+        assert_eq!(players, [0, 2, 1, 3]);
+        assert_eq!(seat_order[0].score, 5_000 + 30_000);
+        assert_eq!(seat_order[1].score, 5_000 + 10_000);
+
+        let split = standings(&points, &uma, TieBreak::Split).unwrap();
+        let places: Vec<usize> = split.iter().map(|standing| standing.place).collect();
+        assert_eq!(places, [1, 1, 3, 3]);
+        assert_eq!(split[0].score, 5_000 + 20_000);
+        assert_eq!(split[1].score, 5_000 + 20_000);
+        assert_eq!(split[2].score, -5_000 - 20_000);
+
+        // three way tie with oka, the leftover points go to the first in seat order
+        let uma = UmaOka::symmetric(&[15_000, 5_000], 4, 25_000, 30_000).unwrap();
+        let split = standings(&[10_000, 30_000, 30_000, 30_000], &uma, TieBreak::Split).unwrap();
+        let scores: Vec<PointDelta> = split.iter().map(|standing| standing.score).collect();
+        assert_eq!(scores, [11_668, 11_666, 11_666, -35_000]);
+        assert_eq!(scores.iter().sum::<PointDelta>(), 0);
+    }
+
+    #[test]
+    fn invalid_uma() {
+        assert_eq!(
+            UmaOka::symmetric(&[15_000], 4, 25_000, 30_000),
+            Err(HandErr::InvalidUma)
+        );
+        let uma = UmaOka::symmetric(&[15_000, 5_000], 4, 25_000, 30_000).unwrap();
+        assert_eq!(
+            standings(&[35_000, 35_000, 35_000], &uma, TieBreak::Split),
+            Err(HandErr::InvalidUma)
+        );
+    }
+}
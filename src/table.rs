@@ -0,0 +1,170 @@
+use crate::calc::{calculate, is_possible_han_fu, rules_payment, FU_VALUES};
+use crate::hand::error::HandErr;
+use crate::limit_hand::LimitHands;
+use crate::lookup::ShownPayment;
+use crate::payment::Payment;
+use crate::ruleset::Ruleset;
+use crate::score::{FuValue, HanValue, HonbaCounter};
+
+/// Han of the limit hands listed after the han and fu, without aotenjou.
+const LIMIT_HAN: [HanValue; 5] = [5, 6, 8, 11, 13];
+
+/// One row of a score table, `None` where the hand can't be won that way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableRow {
+    pub han: HanValue,
+    /// `None` for a limit hand any fu scores.
+    pub fu: Option<FuValue>,
+    pub limit: Option<LimitHands>,
+    pub dealer_ron: Option<ShownPayment>,
+    pub dealer_tsumo: Option<ShownPayment>,
+    pub non_dealer_ron: Option<ShownPayment>,
+    pub non_dealer_tsumo: Option<ShownPayment>,
+}
+
+impl TableRow {
+    fn new(
+        han: HanValue,
+        fu: Option<FuValue>,
+        payment: &Payment,
+        honba: HonbaCounter,
+        rules: &Ruleset,
+    ) -> Result<Self, HandErr> {
+        let possible = |tsumo| fu.is_none_or(|fu| is_possible_han_fu(han, fu, tsumo));
+        let shown = |dealer, tsumo| {
+            possible(tsumo)
+                .then(|| ShownPayment::from_payment(payment, dealer, tsumo, honba))
+                .transpose()
+        };
+        Ok(Self {
+            han,
+            fu,
+            limit: LimitHands::get_limit_hand(han, fu.unwrap_or(30), rules),
+            dealer_ron: shown(true, false)?,
+            dealer_tsumo: shown(true, true)?,
+            non_dealer_ron: shown(false, false)?,
+            non_dealer_tsumo: shown(false, true)?,
+        })
+    }
+}
+
+/// Make the table of payments for every han and fu up to `max_han`, followed by the limit hands above it without aotenjou.
+///
+/// Fails when a payment with the honba is too large to count.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::lookup::ShownPayment;
+/// use mahc::ruleset::Ruleset;
+/// use mahc::table::score_table;
+///
+/// let table = score_table(4, 0, &Ruleset::default()).unwrap();
+/// let row = table.iter().find(|row| row.han == 2 && row.fu == Some(40)).unwrap();
+/// assert_eq!(row.non_dealer_ron, Some(ShownPayment::Ron(2_600)));
+/// assert_eq!(row.dealer_tsumo, Some(ShownPayment::TsumoAll(1_300)));
+///
+/// // 20 fu is only won by tsumo
+/// let row = table.iter().find(|row| row.han == 2 && row.fu == Some(20)).unwrap();
+/// assert_eq!(row.non_dealer_ron, None);
+/// ```
+pub fn score_table(
+    max_han: HanValue,
+    honba: HonbaCounter,
+    rules: &Ruleset,
+) -> Result<Vec<TableRow>, HandErr> {
+    let mut out = vec![];
+    for han in 1..=max_han {
+        for fu in FU_VALUES {
+            if !is_possible_han_fu(han, fu, false) && !is_possible_han_fu(han, fu, true) {
+                continue;
+            }
+            if let Ok(payment) = calculate(han, fu, rules) {
+                out.push(TableRow::new(han, Some(fu), &payment, honba, rules)?);
+            }
+        }
+    }
+
+    if rules.aotenjou {
+        return Ok(out);
+    }
+    // limit hands up to max_han already have their han and fu rows
+    for han in LIMIT_HAN.into_iter().filter(|&han| han > max_han) {
+        let Ok(payment) = calculate(han, 30, rules) else {
+            continue;
+        };
+        out.push(TableRow::new(han, None, &payment, honba, rules)?);
+    }
+    // without kazoe yakuman 13 han is a sanbaiman, so a yakuman gets its own row
+    if !rules.kazoe_yakuman {
+        let yakuman = LimitHands::Yakuman(1);
+        let payment = rules_payment(yakuman.get_score(), rules);
+        let mut row = TableRow::new(13, None, &payment, honba, rules)?;
+        row.limit = Some(yakuman);
+        out.push(row);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(table: &[TableRow], han: HanValue, fu: Option<FuValue>) -> &TableRow {
+        table
+            .iter()
+            .find(|row| row.han == han && row.fu == fu)
+            .unwrap()
+    }
+
+    #[test]
+    fn table_rows() {
+        let table = score_table(4, 1, &Ruleset::default()).unwrap();
+        let pinfu = row(&table, 2, Some(20));
+        assert_eq!(
+            pinfu.non_dealer_tsumo,
+            Some(ShownPayment::Tsumo {
+                non_dealer: 500,
+                dealer: 800
+            })
+        );
+        assert_eq!(row(&table, 2, Some(25)).non_dealer_tsumo, None);
+        assert_eq!(row(&table, 4, Some(30)).limit, None);
+        assert_eq!(row(&table, 13, None).limit, Some(LimitHands::KazoeYakuman));
+        assert_eq!(
+            table.last().unwrap().dealer_ron,
+            Some(ShownPayment::Ron(48_300))
+        );
+    }
+
+    #[test]
+    fn ruleset_tables() {
+        let wrc = score_table(4, 0, &Ruleset::wrc()).unwrap();
+        assert_eq!(row(&wrc, 4, Some(30)).limit, Some(LimitHands::Mangan));
+        assert_eq!(
+            row(&wrc, 4, Some(30)).non_dealer_ron,
+            Some(ShownPayment::Ron(8_000))
+        );
+        assert_eq!(wrc.last().unwrap().limit, Some(LimitHands::Yakuman(1)));
+
+        let table = score_table(6, 0, &Ruleset::default()).unwrap();
+        for han in [5, 6] {
+            let rows = table.iter().filter(|row| row.han == han);
+            assert!(rows.clone().all(|row| row.fu.is_some()));
+            assert_eq!(rows.count(), FU_VALUES.len());
+        }
+        assert_eq!(row(&table, 6, Some(30)).limit, Some(LimitHands::Haneman));
+        assert_eq!(row(&table, 8, None).limit, Some(LimitHands::Baiman));
+
+        let aotenjou = Ruleset {
+            aotenjou: true,
+            ..Ruleset::default()
+        };
+        let table = score_table(6, 0, &aotenjou).unwrap();
+        assert!(table.iter().all(|row| row.fu.is_some()));
+        assert_eq!(
+            row(&table, 6, Some(30)).non_dealer_ron,
+            Some(ShownPayment::Ron(30_800))
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use crate::calc::{self, BestScore};
+use crate::context::WinContext;
+use crate::decompose::{decompose, decompose_hand, parse_tiles};
+use crate::hand::error::HandErr;
+use crate::ruleset::Ruleset;
+use crate::shanten::Shanten;
+use crate::tile_group::{GroupType, TileGroup, TILE_KINDS};
+
+/// A tile that completes a tenpai hand and the score of winning on it.
+#[derive(Debug)]
+pub struct TenpaiWait {
+    /// The winning tile.
+    pub tile: TileGroup,
+    /// The best score when winning on the tile by ron.
+    pub ron: Result<BestScore, HandErr>,
+    /// The best score when winning on the tile by tsumo.
+    pub tsumo: Result<BestScore, HandErr>,
+}
+
+impl TenpaiWait {
+    /// Check if winning on the tile by ron gives no yaku.
+    ///
+    /// A closed hand can still win by tsumo with menzen tsumo.
+    pub fn is_yakunashi(&self) -> bool {
+        matches!(self.ron, Err(HandErr::NoYaku))
+    }
+}
+
+/// List every tile that completes a 13 tile tenpai hand, scored by ron and tsumo in the given context and ruleset.
+///
+/// A tile the hand already holds all four of is not a wait.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::context::WinContext;
+/// use mahc::ruleset::Ruleset;
+/// use mahc::tenpai::tenpai_waits;
+///
+/// let waits = tenpai_waits(
+///     "23m456p789s11z",
+///     vec!["rrrdo".to_string()],
+///     "Ew".to_string(),
+///     "Sw".to_string(),
+///     &WinContext::default(),
+///     &Ruleset::default(),
+/// )
+/// .unwrap();
+/// let tiles: Vec<String> = waits.iter().map(|wait| wait.tile.to_string()).collect();
+/// assert_eq!(tiles, vec!["1m", "4m"]);
+/// assert!(!waits[0].is_yakunashi());
+/// ```
+pub fn tenpai_waits(
+    tiles: &str,
+    calls: Vec<String>,
+    prev: String,
+    seat: String,
+    context: &WinContext,
+    rules: &Ruleset,
+) -> Result<Vec<TenpaiWait>, HandErr> {
+    let closed = parse_tiles(tiles)?;
+    let mut called: Vec<TileGroup> = vec![];
+    for call in calls.clone() {
+        called.push(call.try_into()?);
+    }
+    if closed.len() + called.len() * 3 != 13 {
+        return Err(HandErr::InvalidShape);
+    }
+    if Shanten::new(&closed, &called)?.min() != 0 {
+        return Err(HandErr::NotTenpai);
+    }
+
+    let mut waits = vec![];
+    for tile in wait_tiles(&closed, &called)? {
+        if rules.sanma.is_some() && tile.is_sanma_excluded() {
+            continue;
+        }
+        let hands = decompose_hand(
+            tiles,
+            calls.clone(),
+            tile.to_string(),
+            prev.clone(),
+            seat.clone(),
+        )?;
+
+        let score = |tsumo: bool| {
+            let context = context.to_builder().tsumo(tsumo).build()?;
+            calc::get_best_hand_score(hands.clone(), &context, rules)
+        };
+        waits.push(TenpaiWait {
+            tile,
+            ron: score(false),
+            tsumo: score(true),
+        });
+    }
+
+    Ok(waits)
+}
+
+/// Get every tile that completes a 13 tile hand.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::decompose::parse_tiles;
+/// use mahc::tenpai::wait_tiles;
+///
+/// let closed = parse_tiles("2345678m456p789s").unwrap();
+/// let waits: Vec<String> = wait_tiles(&closed, &[])
+///     .unwrap()
+///     .iter()
+///     .map(|tile| tile.to_string())
+///     .collect();
+/// assert_eq!(waits, vec!["2m", "5m", "8m"]);
+/// ```
+pub fn wait_tiles(closed: &[TileGroup], calls: &[TileGroup]) -> Result<Vec<TileGroup>, HandErr> {
+    if closed.len() + calls.len() * 3 != 13 {
+        return Err(HandErr::InvalidShape);
+    }
+
+    let mut waits = vec![];
+    for index in 0..TILE_KINDS {
+        let tile = TileGroup::from_index(index, GroupType::None)?;
+        match decompose(closed, calls, &tile) {
+            Ok(_) => waits.push(tile),
+            Err(HandErr::InvalidShape) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(waits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waits(tiles: &str, calls: &[&str], riichi: bool) -> Result<Vec<TenpaiWait>, HandErr> {
+        tenpai_waits(
+            tiles,
+            calls.iter().map(|call| call.to_string()).collect(),
+            "Ew".to_string(),
+            "Sw".to_string(),
+            &WinContext::builder().riichi(riichi).build().unwrap(),
+            &Ruleset::default(),
+        )
+    }
+
+    fn wait_tiles(waits: &[TenpaiWait]) -> Vec<String> {
+        waits.iter().map(|wait| wait.tile.to_string()).collect()
+    }
+
+    #[test]
+    fn multiple_waits() {
+        let out = waits("2345678m456p789s", &[], false).unwrap();
+        assert_eq!(wait_tiles(&out), vec!["2m", "5m", "8m"]);
+
+        let out = waits("1112345678999m", &[], false).unwrap();
+        assert_eq!(wait_tiles(&out).len(), 9);
+        assert!(out.iter().all(|wait| wait.ron.is_ok()));
+
+        let out = waits("19m19p19s1234567z", &[], false).unwrap();
+        assert_eq!(wait_tiles(&out).len(), 13);
+    }
+
+    #[test]
+    fn wait_scores() {
+        let out = waits("234m456p789s1144z", &[], false).unwrap();
+        assert_eq!(wait_tiles(&out), vec!["Ew", "Nw"]);
+
+        // east is the prevalent wind, winning on it makes yakuhai
+        let ron = out[0].ron.as_ref().unwrap();
+        assert_eq!(ron.score.han(), 1);
+        let tsumo = out[0].tsumo.as_ref().unwrap();
+        assert_eq!(tsumo.score.han(), 2);
+
+        // north is neither wind, ron has no yaku
+        assert!(out[1].is_yakunashi());
+        assert_eq!(out[1].tsumo.as_ref().unwrap().score.han(), 1);
+    }
+
+    #[test]
+    fn riichi_removes_yakunashi() {
+        let out = waits("234m456p789s1144z", &[], true).unwrap();
+        assert!(out.iter().all(|wait| !wait.is_yakunashi()));
+    }
+
+    #[test]
+    fn open_hand_yakunashi() {
+        let out = waits("23m456p789s11z", &["555so"], false).unwrap();
+        assert_eq!(wait_tiles(&out), vec!["1m", "4m"]);
+        assert!(out[0].is_yakunashi());
+        assert_eq!(out[0].tsumo.as_ref().unwrap_err(), &HandErr::NoYaku);
+    }
+
+    #[test]
+    fn not_tenpai() {
+        assert_eq!(
+            waits("2468m456p789s112z", &[], false).unwrap_err(),
+            HandErr::NotTenpai
+        );
+        assert_eq!(
+            waits("2345678m456p789s1z", &[], false).unwrap_err(),
+            HandErr::InvalidShape
+        );
+    }
+}
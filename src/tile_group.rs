@@ -1,6 +1,9 @@
 use crate::hand::error::HandErr;
 use crate::suit::Suit;
 
+/// Number of distinct tile kinds: nine of each numbered suit, four winds and three dragons.
+pub const TILE_KINDS: usize = 34;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TileGroup {
     pub value: String,
@@ -138,15 +141,147 @@ impl TileGroup {
                 _ => return Err(HandErr::InvalidGroup),
             },
         };
-        Ok(Self::new(
+        Self::new(
             value,
             self.suit.clone(),
             false,
             self.group_type.clone(),
             false,
             false,
-        )?)
+        )
+    }
+
+    /// Get the tile after this one for a sanma dora indicator, where 1m points to 9m.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::tile_group::TileGroup;
+    /// let tile: TileGroup = "1m".to_string().try_into().unwrap();
+    /// assert_eq!(tile.next_tile_sanma().unwrap().to_string(), "9m");
+    /// ```
+    pub fn next_tile_sanma(&self) -> Result<Self, HandErr> {
+        if self.suit == Suit::Manzu && self.value == "1" {
+            return Self::from_index(8, self.group_type.clone());
+        }
+        self.next_tile()
+    }
+
+    /// Check if the group holds any of 2m to 8m, which are left out of the sanma tile set.
+    pub fn is_sanma_excluded(&self) -> bool {
+        self.suit == Suit::Manzu
+            && self
+                .tile_indices()
+                .iter()
+                .any(|index| (1..=7).contains(index))
     }
+
+    /// Get the tile kind index (`0..TILE_KINDS`) of the first tile in the group.
+    ///
+    /// Tiles are ordered manzu, pinzu, souzu (1-9 each), then the winds (east, south, west, north) and the dragons (white, green, red).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::tile_group::TileGroup;
+    /// let tile: TileGroup = "3p".to_string().try_into().unwrap();
+    /// assert_eq!(tile.tile_index(), 11);
+    ///
+    /// let tile: TileGroup = "rd".to_string().try_into().unwrap();
+    /// assert_eq!(tile.tile_index(), 33);
+    /// ```
+    pub fn tile_index(&self) -> usize {
+        match self.suit {
+            Suit::Manzu => self.parse_u8().unwrap() as usize - 1,
+            Suit::Pinzu => self.parse_u8().unwrap() as usize + 8,
+            Suit::Souzu => self.parse_u8().unwrap() as usize + 17,
+            Suit::Wind => 27 + "ESWN".find(self.value.as_str()).unwrap(),
+            Suit::Dragon => 31 + "wgr".find(self.value.as_str()).unwrap(),
+        }
+    }
+
+    /// Get the tile kind index of every tile in the group.
+    pub fn tile_indices(&self) -> Vec<usize> {
+        let index = self.tile_index();
+        match self.group_type {
+            GroupType::Sequence => vec![index, index + 1, index + 2],
+            _ => vec![index; self.group_type.tile_count() as usize],
+        }
+    }
+
+    /// Create a closed group of the given type starting at the tile kind index.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mahc::tile_group::{GroupType, TileGroup};
+    /// let group = TileGroup::from_index(19, GroupType::Sequence).unwrap();
+    /// assert_eq!(group.to_string(), "234s");
+    /// ```
+    pub fn from_index(index: usize, group_type: GroupType) -> Result<Self, HandErr> {
+        if index >= TILE_KINDS {
+            return Err(HandErr::InvalidGroup);
+        }
+        let (values, suit) = match index {
+            0..=8 => ("123456789", "m"),
+            9..=17 => ("123456789", "p"),
+            18..=26 => ("123456789", "s"),
+            27..=30 => ("ESWN", "w"),
+            _ => ("wgr", "d"),
+        };
+        let offset = match index {
+            0..=26 => index % 9,
+            27..=30 => index - 27,
+            _ => index - 31,
+        };
+        let value = &values[offset..offset + 1];
+        let notation = match group_type {
+            GroupType::Sequence => values
+                .get(offset..offset + 3)
+                .filter(|_| suit != "w" && suit != "d")
+                .ok_or(HandErr::InvalidGroup)?
+                .to_string(),
+            _ => value.repeat(group_type.tile_count() as usize),
+        };
+
+        format!("{notation}{suit}").try_into()
+    }
+}
+
+impl std::fmt::Display for TileGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut values: String = match self.group_type {
+            GroupType::Sequence => {
+                let start = self.parse_u8().unwrap();
+                (start..start + 3).map(|v| v.to_string()).collect()
+            }
+            _ => self.value.repeat(self.group_type.tile_count() as usize),
+        };
+        if self.isaka {
+            values = values.replacen('5', "0", 1);
+        }
+        let suit = match self.suit {
+            Suit::Manzu => "m",
+            Suit::Pinzu => "p",
+            Suit::Souzu => "s",
+            Suit::Wind => "w",
+            Suit::Dragon => "d",
+        };
+        let open = if self.isopen { "o" } else { "" };
+
+        write!(f, "{values}{suit}{open}")
+    }
+}
+
+/// Count how many of each tile kind the groups contain, indexed by [`TileGroup::tile_index`].
+pub fn tile_counts(groups: &[TileGroup]) -> [u8; TILE_KINDS] {
+    let mut counts = [0; TILE_KINDS];
+    for group in groups {
+        for index in group.tile_indices() {
+            counts[index] += 1;
+        }
+    }
+    counts
 }
 
 //AHAHAHAHAHAHAHAH I DONT NEED THIS
@@ -205,9 +340,9 @@ impl GroupType {
                     .collect::<std::collections::HashSet<&str>>()
                     .contains(group.get(0..count).unwrap())
                 {
-                    return Ok(Self::Sequence);
+                    Ok(Self::Sequence)
                 } else {
-                    return Err(HandErr::InvalidGroup);
+                    Err(HandErr::InvalidGroup)
                 }
             }
             4 => Ok(Self::Kan),
@@ -262,7 +397,7 @@ mod tests {
         assert_eq!(tile.value, "E");
         assert!(!tile.isopen);
         assert_eq!(tile.group_type, GroupType::None);
-        assert_eq!(tile.isterminal, false);
+        assert!(!tile.isterminal);
 
         let tile = TileGroup::try_from("222zo".to_string()).unwrap();
         assert!(tile.isopen);
@@ -326,17 +461,17 @@ mod tests {
     fn is_akadora_from_string() {
         let tile = TileGroup::try_from("0m".to_string()).unwrap();
         assert_eq!(tile.value, "5");
-        assert_eq!(tile.isaka, true);
+        assert!(tile.isaka);
         assert_eq!(tile.group_type, GroupType::None);
 
         let tile = TileGroup::try_from("055m".to_string()).unwrap();
         assert_eq!(tile.value, "5");
-        assert_eq!(tile.isaka, true);
+        assert!(tile.isaka);
         assert_eq!(tile.group_type, GroupType::Triplet);
 
         let tile = TileGroup::try_from("406m".to_string()).unwrap();
         assert_eq!(tile.value, "4");
-        assert_eq!(tile.isaka, true);
+        assert!(tile.isaka);
         assert_eq!(tile.group_type, GroupType::Sequence);
     }
 
@@ -344,7 +479,7 @@ mod tests {
     fn is_not_akadora_from_string() {
         let tile = TileGroup::try_from("1m".to_string()).unwrap();
         assert_eq!(tile.value, "1");
-        assert_eq!(tile.isaka, false);
+        assert!(!tile.isaka);
         assert_eq!(tile.group_type, GroupType::None);
     }
 
@@ -379,6 +514,36 @@ mod tests {
         assert_eq!(next_tile.value, "E");
     }
 
+    #[test]
+    fn tile_index_round_trip() {
+        for index in 0..TILE_KINDS {
+            let tile = TileGroup::from_index(index, GroupType::None).unwrap();
+            assert_eq!(tile.tile_index(), index);
+        }
+        let tile = TileGroup::from_index(27, GroupType::Triplet).unwrap();
+        assert_eq!(tile.to_string(), "EEEw");
+        let tile = TileGroup::from_index(31, GroupType::Pair).unwrap();
+        assert_eq!(tile.to_string(), "wwd");
+        assert_eq!(
+            TileGroup::from_index(7, GroupType::Sequence),
+            Err(HandErr::InvalidGroup)
+        );
+        assert_eq!(
+            TileGroup::from_index(28, GroupType::Sequence),
+            Err(HandErr::InvalidGroup)
+        );
+    }
+
+    #[test]
+    fn tilegroup_to_string() {
+        let tile = TileGroup::try_from("406so".to_string()).unwrap();
+        assert_eq!(tile.to_string(), "406so");
+        assert_eq!(tile.tile_indices(), vec![21, 22, 23]);
+        let tile = TileGroup::try_from("7777z".to_string()).unwrap();
+        assert_eq!(tile.to_string(), "rrrrd");
+        assert_eq!(tile.tile_indices(), vec![33; 4]);
+    }
+
     #[test]
     fn next_manpinsou() {
         let tile = TileGroup::try_from("1m".to_string()).unwrap();
@@ -0,0 +1,176 @@
+use crate::hand::error::HandErr;
+use crate::shanten::{hand_counts, Shanten};
+use crate::tile_group::{tile_counts, GroupType, TileGroup, TILE_KINDS};
+
+/// A tile that lowers the shanten of a hand and how many copies of it are left.
+#[derive(Debug, PartialEq)]
+pub struct Improvement {
+    /// The improving tile.
+    pub tile: TileGroup,
+    /// Copies of the tile that have not been seen.
+    pub live: u8,
+}
+
+/// A discard from a 14 tile hand and the tiles that improve the hand left behind.
+#[derive(Debug, PartialEq)]
+pub struct Discard {
+    /// The discarded tile.
+    pub tile: TileGroup,
+    /// Shanten of the hand after the discard.
+    pub shanten: i8,
+    /// Every tile that lowers the shanten after the discard.
+    pub improvements: Vec<Improvement>,
+}
+
+impl Discard {
+    /// Get the number of live tiles that improve the hand (the ukeire).
+    pub fn ukeire(&self) -> u8 {
+        self.improvements
+            .iter()
+            .map(|improvement| improvement.live)
+            .sum()
+    }
+}
+
+/// Analyse every discard of a 14 tile hand, from the most efficient to the least.
+///
+/// The tiles in the hand, the called melds, the dora indicators and the other visible tiles are not live.
+/// Discards are ranked on the shanten left after them, then the number of live improving tiles.
+///
+/// # Examples
+///
+/// ```rust
+/// use mahc::decompose::parse_tiles;
+/// use mahc::ukeire::ukeire;
+///
+/// let closed = parse_tiles("123m456p789s111z34z").unwrap();
+/// let discards = ukeire(&closed, &[], &[], &[]).unwrap();
+///
+/// // keeping either honour as a tanki wait leaves three copies to win on
+/// assert_eq!(discards[0].shanten, 0);
+/// assert_eq!(discards[0].ukeire(), 3);
+/// ```
+pub fn ukeire(
+    closed: &[TileGroup],
+    calls: &[TileGroup],
+    dora_indicators: &[TileGroup],
+    visible: &[TileGroup],
+) -> Result<Vec<Discard>, HandErr> {
+    let mut counts = hand_counts(closed, calls)?;
+    if counts.iter().sum::<u8>() as usize + calls.len() * 3 != 14 {
+        return Err(HandErr::InvalidShape);
+    }
+
+    let called = tile_counts(calls);
+    let indicators = tile_counts(dora_indicators);
+    let seen = tile_counts(visible);
+    let live: Vec<u8> = (0..TILE_KINDS)
+        .map(|i| 4u8.saturating_sub(counts[i] + called[i] + indicators[i] + seen[i]))
+        .collect();
+
+    let mut discards = vec![];
+    for discard in 0..TILE_KINDS {
+        if counts[discard] == 0 {
+            continue;
+        }
+        counts[discard] -= 1;
+        let shanten = Shanten::from_counts(&counts, calls.len()).min();
+
+        let mut improvements = vec![];
+        for draw in 0..TILE_KINDS {
+            // drawing back the discard or a fifth copy is not an improvement
+            if draw == discard || counts[draw] + called[draw] >= 4 {
+                continue;
+            }
+            counts[draw] += 1;
+            if Shanten::from_counts(&counts, calls.len()).min() < shanten {
+                improvements.push(Improvement {
+                    tile: TileGroup::from_index(draw, GroupType::None)?,
+                    live: live[draw],
+                });
+            }
+            counts[draw] -= 1;
+        }
+        counts[discard] += 1;
+
+        discards.push(Discard {
+            tile: TileGroup::from_index(discard, GroupType::None)?,
+            shanten,
+            improvements,
+        });
+    }
+
+    discards.sort_by_key(|discard| (discard.shanten, std::cmp::Reverse(discard.ukeire())));
+
+    Ok(discards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decompose::parse_tiles;
+
+    fn tiles(tiles: &str) -> Vec<TileGroup> {
+        parse_tiles(tiles).unwrap()
+    }
+
+    fn improving_tiles(discard: &Discard) -> Vec<String> {
+        discard
+            .improvements
+            .iter()
+            .map(|improvement| improvement.tile.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn best_discard_first() {
+        let out = ukeire(&tiles("234m456p789s2378m1z"), &[], &[], &[]).unwrap();
+        assert_eq!(out[0].tile.to_string(), "Ew");
+        assert_eq!(out[0].shanten, 1);
+        assert_eq!(
+            improving_tiles(&out[0]),
+            vec!["1m", "2m", "3m", "4m", "5m", "6m", "7m", "8m", "9m"]
+        );
+        // tiles held in the hand are not live
+        assert_eq!(out[0].ukeire(), 4 + 2 + 2 + 3 + 4 + 4 + 3 + 3 + 4);
+
+        let last = out.last().unwrap();
+        assert_eq!(last.shanten, 2);
+    }
+
+    #[test]
+    fn visible_tiles_are_not_live() {
+        let closed = tiles("123m456p789s111z34z");
+        let out = ukeire(&closed, &[], &tiles("3z"), &tiles("4z4z4z")).unwrap();
+        // the 3z tanki has two copies left, the 4z tanki none
+        assert_eq!(out[0].tile.to_string(), "Nw");
+        assert_eq!(out[0].ukeire(), 2);
+        assert_eq!(out[1].tile.to_string(), "Ww");
+        assert_eq!(out[1].ukeire(), 0);
+        assert_eq!(improving_tiles(&out[1]), vec!["Nw"]);
+    }
+
+    #[test]
+    fn ukeire_with_calls() {
+        let calls: Vec<TileGroup> = vec!["rrrdo".to_string().try_into().unwrap()];
+        let out = ukeire(&tiles("23m456p789s11z9p"), &calls, &[], &[]).unwrap();
+        assert_eq!(out[0].tile.to_string(), "9p");
+        assert_eq!(out[0].shanten, 0);
+        assert_eq!(improving_tiles(&out[0]), vec!["1m", "4m"]);
+    }
+
+    #[test]
+    fn complete_hand_keeps_winning_tile() {
+        let out = ukeire(&tiles("123m456p789s11222z"), &[], &[], &[]).unwrap();
+        assert_eq!(out[0].shanten, 0);
+        assert!(out.iter().all(|discard| discard.shanten >= 0));
+    }
+
+    #[test]
+    fn ukeire_invalid_input() {
+        assert_eq!(
+            ukeire(&tiles("123m456p789s1122z"), &[], &[], &[]).unwrap_err(),
+            HandErr::InvalidShape
+        );
+    }
+}
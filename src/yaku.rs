@@ -1,6 +1,6 @@
 use crate::score::HanValue;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Yaku {
     // One Han Yaku
     Tanyao,   //done
@@ -11,6 +11,7 @@ pub enum Yaku {
     Riichi,  //(bascially done by default)
     Ippatsu, //(bascially done by default)
     Haitei,
+    Houtei,
     RinshanKaihou,
     Chankan,
 
@@ -53,6 +54,9 @@ pub enum Yaku {
     Suukantsu,
     Tenhou,
     Chiihou,
+
+    // Scored from the discards
+    NagashiMangan,
 }
 
 impl Yaku {
@@ -67,6 +71,7 @@ impl Yaku {
             Self::Riichi => "Riichi: 1",
             Self::Ippatsu => "Ippatsu: 1",
             Self::Haitei => "Haitei: 1",
+            Self::Houtei => "Houtei: 1",
             Self::RinshanKaihou => "RinshanKaihou: 1",
             Self::Chankan => "Chankan: 1",
 
@@ -141,6 +146,7 @@ impl Yaku {
             Self::SuuankouTankiWait => "Suuankou Yakuman Tanki Wait ",
             Self::Daichiishin => "Daichiishin Yakuman",
             Self::ChuurenPoutou9SidedWait => "ChuurenPoutou Yakuman 9 sided wait ",
+            Self::NagashiMangan => "NagashiMangan: Mangan",
         }
         .to_string()
     }
@@ -156,6 +162,7 @@ impl Yaku {
             | Self::Riichi
             | Self::Ippatsu
             | Self::Haitei
+            | Self::Houtei
             | Self::RinshanKaihou
             | Self::Chankan => 1,
 
@@ -219,6 +226,8 @@ impl Yaku {
             | Self::Chiihou
             | Self::SuuankouTankiWait
             | Self::Daichiishin => 1,
+
+            Self::NagashiMangan => 5,
         }
     }
 